@@ -22,6 +22,7 @@ in
       fileset = lib.fileset.unions [
         ../src
         ../config.toml
+        ../assets
       ];
     };
   })
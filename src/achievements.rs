@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::{aoc::models::AocId, utils::store::Store};
+
+const ACHIEVEMENTS_STORE_KEY: &[u8] = b"achievements";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnlockedAchievement {
+    pub id: String,
+    pub year: i32,
+    pub unlocked_at: DateTime<Utc>,
+}
+
+/// Achievements unlocked by members, keyed by canonical AoC id.
+pub struct Achievements {
+    unlocked: RwLock<HashMap<AocId, Vec<UnlockedAchievement>>>,
+    store: Store,
+}
+
+impl Achievements {
+    pub async fn new(store: Store) -> anyhow::Result<Self> {
+        let unlocked = store
+            .get::<HashMap<AocId, Vec<UnlockedAchievement>>>(ACHIEVEMENTS_STORE_KEY)
+            .await?
+            .unwrap_or_default();
+
+        Ok(Self {
+            unlocked: unlocked.into(),
+            store,
+        })
+    }
+
+    pub async fn get(&self, member: AocId) -> Vec<UnlockedAchievement> {
+        self.unlocked
+            .read()
+            .await
+            .get(&member)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub async fn get_all(&self) -> HashMap<AocId, Vec<UnlockedAchievement>> {
+        self.unlocked.read().await.clone()
+    }
+
+    /// Store the given achievements and return those that have not been
+    /// unlocked before.
+    pub async fn unlock(
+        &self,
+        achievements: impl IntoIterator<Item = (AocId, UnlockedAchievement)>,
+    ) -> anyhow::Result<Vec<(AocId, UnlockedAchievement)>> {
+        let mut guard = self.unlocked.write().await;
+        let mut new = Vec::new();
+        for (member, achievement) in achievements {
+            let unlocked = guard.entry(member).or_default();
+            if unlocked
+                .iter()
+                .any(|a| a.id == achievement.id && a.year == achievement.year)
+            {
+                continue;
+            }
+            unlocked.push(achievement.clone());
+            new.push((member, achievement));
+        }
+
+        if !new.is_empty() {
+            self.store
+                .set::<HashMap<AocId, Vec<UnlockedAchievement>>>(ACHIEVEMENTS_STORE_KEY, &guard)
+                .await?;
+        }
+
+        Ok(new)
+    }
+}
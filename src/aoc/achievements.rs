@@ -0,0 +1,154 @@
+use chrono::{FixedOffset, TimeDelta};
+
+use super::{
+    day::AocDay,
+    models::{PrivateLeaderboard, PrivateLeaderboardMember},
+    stats::Streaks,
+};
+use crate::config::AchievementRule;
+
+/// Returns whether the given member currently satisfies the given rule.
+/// `last_day` is the most recently unlocked day of the year.
+pub fn is_satisfied(
+    rule: AchievementRule,
+    leaderboard: &PrivateLeaderboard,
+    member: &PrivateLeaderboardMember,
+    year: i32,
+    last_day: u32,
+    timezone: FixedOffset,
+) -> bool {
+    match rule {
+        AchievementRule::FirstSolve => {
+            let n = leaderboard.members.len() as u32;
+            member
+                .completion_day_level
+                .keys()
+                .any(|&day| leaderboard.star_score(member, day, true) == Some(n))
+        }
+        AchievementRule::Stars { count } => member.stars >= count,
+        AchievementRule::SolveWithin { days, seconds } => {
+            member
+                .completion_day_level
+                .keys()
+                .filter(|&&day| {
+                    member.star_ts(day, true).is_some_and(|ts| {
+                        ts - AocDay { year, day }.unlock_datetime() <= TimeDelta::seconds(seconds)
+                    })
+                })
+                .count()
+                >= days
+        }
+        // the fastest gap of a running event may still be beaten
+        AchievementRule::FastestGap => {
+            AocDay::is_over(year)
+                && fastest_gap(member).is_some_and(|gap| {
+                    leaderboard
+                        .members
+                        .values()
+                        .filter_map(fastest_gap)
+                        .all(|other| gap <= other)
+                })
+        }
+        AchievementRule::Streak { days } => {
+            Streaks::compute(member, year, last_day, timezone)
+                .solved
+                .best
+                >= days
+        }
+    }
+}
+
+/// Returns the smallest gap between part one and part two of any day.
+fn fastest_gap(member: &PrivateLeaderboardMember) -> Option<TimeDelta> {
+    member
+        .completion_day_level
+        .values()
+        .filter_map(|c| Some(c.snd.as_ref()?.get_star_ts - c.fst.get_star_ts))
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aoc::models::testing::{leaderboard, member},
+        utils::datetime::set_now,
+    };
+
+    #[test]
+    fn is_satisfied() {
+        let _guard = set_now("2025-01-01T00:00:00Z".parse().unwrap());
+        let unlock = |day| AocDay { year: 2024, day }.unlock_datetime().timestamp();
+        let mut m1 = member(
+            1,
+            &[
+                (1, unlock(1) + 60, Some(unlock(1) + 600)),
+                (2, unlock(2) + 60, Some(unlock(2) + 7200)),
+            ],
+        );
+        m1.stars = 4;
+        let mut m2 = member(
+            2,
+            &[
+                (1, unlock(1) + 300, Some(unlock(1) + 700)),
+                (2, unlock(2) + 30, None),
+            ],
+        );
+        m2.stars = 3;
+        let leaderboard = leaderboard(&[m1.clone(), m2.clone()]);
+        let tz = FixedOffset::east_opt(0).unwrap();
+        let check = |rule, member| super::is_satisfied(rule, &leaderboard, member, 2024, 2, tz);
+
+        assert!(check(AchievementRule::FirstSolve, &m1));
+        assert!(!check(AchievementRule::FirstSolve, &m2));
+
+        assert!(check(AchievementRule::Stars { count: 4 }, &m1));
+        assert!(!check(AchievementRule::Stars { count: 4 }, &m2));
+
+        let within = |days| AchievementRule::SolveWithin {
+            days,
+            seconds: 3600,
+        };
+        assert!(check(within(1), &m1));
+        assert!(!check(within(2), &m1));
+        assert!(check(within(1), &m2));
+
+        assert!(!check(AchievementRule::FastestGap, &m1));
+        assert!(check(AchievementRule::FastestGap, &m2));
+
+        assert!(check(AchievementRule::Streak { days: 2 }, &m1));
+        assert!(!check(AchievementRule::Streak { days: 3 }, &m1));
+    }
+
+    #[test]
+    fn fastest_gap_after_event() {
+        let unlock = |day| AocDay { year: 2024, day }.unlock_datetime().timestamp();
+        let m1 = member(1, &[(1, unlock(1) + 60, Some(unlock(1) + 120))]);
+        let m2 = member(2, &[(1, unlock(1) + 30, Some(unlock(1) + 300))]);
+        let tz = FixedOffset::east_opt(0).unwrap();
+
+        // m1 leads after day 1, but the event is still running
+        let _guard = set_now(AocDay { year: 2024, day: 2 }.unlock_datetime());
+        let lb = leaderboard(&[m1.clone(), m2.clone()]);
+        let check = |lb, m| super::is_satisfied(AchievementRule::FastestGap, lb, m, 2024, 2, tz);
+        assert!(!check(&lb, &m1));
+        assert!(!check(&lb, &m2));
+
+        // m2 takes the lead on day 2 and keeps it until the end of the event
+        let m2 = member(
+            2,
+            &[
+                (1, unlock(1) + 30, Some(unlock(1) + 300)),
+                (2, unlock(2) + 60, Some(unlock(2) + 90)),
+            ],
+        );
+        let lb = leaderboard(&[m1.clone(), m2.clone()]);
+        assert!(!check(&lb, &m1));
+        assert!(!check(&lb, &m2));
+
+        drop(_guard);
+        let _guard = set_now(AocDay::event_end(2024));
+        assert!(!check(&lb, &m1));
+        assert!(check(&lb, &m2));
+    }
+}
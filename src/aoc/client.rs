@@ -1,11 +1,14 @@
 use std::{
     collections::{BTreeMap, HashMap},
-    ops::Bound,
+    ops::{Bound, RangeInclusive},
     time::Duration,
 };
 
 use chrono::{DateTime, Utc};
-use tokio::sync::RwLock;
+use tokio::{
+    sync::{Mutex, RwLock},
+    time::Instant,
+};
 use tracing::trace;
 
 use super::{
@@ -26,6 +29,8 @@ pub struct AocClient {
     whoami: AocWhoami,
     default_cache_ttl: Duration,
     cache_ttl_rules: BTreeMap<i64, Duration>,
+    request_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
     leaderboard_cache: RwLock<LeaderboardCache>,
     store: Store,
 }
@@ -35,6 +40,7 @@ impl AocClient {
         session: &str,
         default_cache_ttl: Duration,
         cache_ttl_rules: BTreeMap<i64, Duration>,
+        request_interval: Duration,
         store: Store,
     ) -> anyhow::Result<Self> {
         let api = AocApiClient::new(session)?;
@@ -51,6 +57,8 @@ impl AocClient {
             whoami,
             default_cache_ttl,
             cache_ttl_rules,
+            request_interval,
+            last_request: Default::default(),
             leaderboard_cache: leaderboard_cache.into(),
             store,
         })
@@ -104,6 +112,9 @@ impl AocClient {
         }
         drop(guard);
 
+        // don't block readers of other years while waiting for the next request
+        self.throttle().await;
+
         let mut guard = self.leaderboard_cache.write().await;
         if let Some(cached) = guard.get(&year).filter(|(_, ts)| now < *ts + ttl) {
             trace!(
@@ -128,74 +139,106 @@ impl AocClient {
         Ok(entry)
     }
 
+    /// Fetch the private leaderboards of all the given years one after another.
+    pub async fn get_private_leaderboards(
+        &self,
+        years: RangeInclusive<i32>,
+    ) -> anyhow::Result<Vec<(i32, PrivateLeaderboard)>> {
+        let mut leaderboards = Vec::new();
+        for year in years {
+            let (leaderboard, _) = self.get_private_leaderboard(year).await?;
+            leaderboards.push((year, leaderboard));
+        }
+        Ok(leaderboards)
+    }
+
+    /// Wait until at least `request_interval` has passed since the last request
+    /// to the AoC api.
+    async fn throttle(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(last_request) = *last_request {
+            tokio::time::sleep_until(last_request + self.request_interval).await;
+        }
+        *last_request = Some(Instant::now());
+    }
+
     pub async fn get_daily_private_leaderboard(
         &self,
         year: i32,
         day: u32,
         parts: Parts,
     ) -> anyhow::Result<(PrivateLeaderboard, DateTime<Utc>)> {
-        let (mut leaderboard, last_update) = self.get_private_leaderboard(year).await?;
-
-        let mut members_by_p1 = leaderboard
-            .members
-            .iter()
-            .filter_map(|(&id, m)| {
-                m.completion_day_level
-                    .get(&day)
-                    .map(|c| (id, m, c.fst.get_star_ts))
-            })
-            .collect::<Vec<_>>();
-        members_by_p1.sort_unstable_by_key(|&(_, m, ts)| (ts, m));
-        let member_ids_and_completion_ts_by_p1 = members_by_p1
-            .into_iter()
-            .map(|(id, _, c)| (id, c))
-            .collect::<Vec<_>>();
-
-        let mut members_by_p2 = leaderboard
-            .members
-            .iter()
-            .filter_map(|(&id, m)| {
-                m.completion_day_level
-                    .get(&day)
-                    .and_then(|c| c.snd.as_ref())
-                    .map(|c| (id, m, c.get_star_ts))
-            })
-            .collect::<Vec<_>>();
-        members_by_p2.sort_unstable_by_key(|&(_, m, ts)| (ts, m));
-        let member_ids_and_completion_ts_by_p2 = members_by_p2
-            .into_iter()
-            .map(|(id, _, c)| (id, c))
-            .collect::<Vec<_>>();
-
-        for m in leaderboard.members.values_mut() {
-            m.global_score = 0;
-            m.local_score = 0;
-            m.stars = 0;
-            m.last_star_ts = Default::default();
-        }
+        let (leaderboard, last_update) = self.get_private_leaderboard(year).await?;
+        Ok((daily_leaderboard(leaderboard, day, parts), last_update))
+    }
+}
 
-        if matches!(parts, Parts::P1 | Parts::Both) {
-            for (i, (id, ts)) in member_ids_and_completion_ts_by_p1.into_iter().enumerate() {
-                let score = leaderboard.members.len() - i;
-                let member = leaderboard.members.get_mut(&id).unwrap();
-                member.local_score += score as u32;
-                member.stars += 1;
-                member.last_star_ts = member.last_star_ts.max(ts);
-            }
-        }
+/// Recompute the scores, stars and last star timestamps of the given
+/// leaderboard, considering only the given parts of the given day.
+pub fn daily_leaderboard(
+    mut leaderboard: PrivateLeaderboard,
+    day: u32,
+    parts: Parts,
+) -> PrivateLeaderboard {
+    let mut members_by_p1 = leaderboard
+        .members
+        .iter()
+        .filter_map(|(&id, m)| {
+            m.completion_day_level
+                .get(&day)
+                .map(|c| (id, m, c.fst.get_star_ts))
+        })
+        .collect::<Vec<_>>();
+    members_by_p1.sort_unstable_by_key(|&(_, m, ts)| (ts, m));
+    let member_ids_and_completion_ts_by_p1 = members_by_p1
+        .into_iter()
+        .map(|(id, _, c)| (id, c))
+        .collect::<Vec<_>>();
+
+    let mut members_by_p2 = leaderboard
+        .members
+        .iter()
+        .filter_map(|(&id, m)| {
+            m.completion_day_level
+                .get(&day)
+                .and_then(|c| c.snd.as_ref())
+                .map(|c| (id, m, c.get_star_ts))
+        })
+        .collect::<Vec<_>>();
+    members_by_p2.sort_unstable_by_key(|&(_, m, ts)| (ts, m));
+    let member_ids_and_completion_ts_by_p2 = members_by_p2
+        .into_iter()
+        .map(|(id, _, c)| (id, c))
+        .collect::<Vec<_>>();
+
+    for m in leaderboard.members.values_mut() {
+        m.global_score = 0;
+        m.local_score = 0;
+        m.stars = 0;
+        m.last_star_ts = Default::default();
+    }
 
-        if matches!(parts, Parts::P2 | Parts::Both) {
-            for (i, (id, ts)) in member_ids_and_completion_ts_by_p2.into_iter().enumerate() {
-                let score = leaderboard.members.len() - i;
-                let member = leaderboard.members.get_mut(&id).unwrap();
-                member.local_score += score as u32;
-                member.stars += 1;
-                member.last_star_ts = member.last_star_ts.max(ts);
-            }
+    if matches!(parts, Parts::P1 | Parts::Both) {
+        for (i, (id, ts)) in member_ids_and_completion_ts_by_p1.into_iter().enumerate() {
+            let score = leaderboard.members.len() - i;
+            let member = leaderboard.members.get_mut(&id).unwrap();
+            member.local_score += score as u32;
+            member.stars += 1;
+            member.last_star_ts = member.last_star_ts.max(ts);
         }
+    }
 
-        Ok((leaderboard, last_update))
+    if matches!(parts, Parts::P2 | Parts::Both) {
+        for (i, (id, ts)) in member_ids_and_completion_ts_by_p2.into_iter().enumerate() {
+            let score = leaderboard.members.len() - i;
+            let member = leaderboard.members.get_mut(&id).unwrap();
+            member.local_score += score as u32;
+            member.stars += 1;
+            member.last_star_ts = member.last_star_ts.max(ts);
+        }
     }
+
+    leaderboard
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
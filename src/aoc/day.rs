@@ -1,6 +1,6 @@
-use chrono::{DateTime, Datelike, TimeZone, Utc};
+use chrono::{DateTime, Datelike, TimeDelta, TimeZone, Utc};
 
-use crate::utils::datetime::{now_est, EST};
+use crate::utils::datetime::{now, now_est, EST};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AocDay {
@@ -9,12 +9,31 @@ pub struct AocDay {
 }
 
 impl AocDay {
+    /// The number of puzzles of each year.
+    pub const DAYS: u32 = 25;
+
     pub fn unlock_datetime(self) -> DateTime<Utc> {
         EST.with_ymd_and_hms(self.year, 12, self.day, 0, 0, 0)
             .unwrap()
             .to_utc()
     }
 
+    /// Returns the end of the event of the given year, i.e. the end of the
+    /// (EST) day on which the last puzzle unlocked.
+    pub fn event_end(year: i32) -> DateTime<Utc> {
+        Self {
+            year,
+            day: Self::DAYS,
+        }
+        .unlock_datetime()
+            + TimeDelta::days(1)
+    }
+
+    /// Returns whether the event of the given year has ended.
+    pub fn is_over(year: i32) -> bool {
+        now() >= Self::event_end(year)
+    }
+
     pub fn url(self) -> String {
         format!("https://adventofcode.com/{}/day/{}", self.year, self.day)
     }
@@ -75,6 +94,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn is_over() {
+        for (now, year, expected) in [
+            ("2024-12-25T06:00:07+01:00", 2024, false),
+            ("2024-12-26T05:59:59+01:00", 2024, false),
+            ("2024-12-26T06:00:00+01:00", 2024, true),
+            ("2024-12-01T06:00:00+01:00", 2023, true),
+            ("2024-12-01T06:00:00+01:00", 2025, false),
+        ] {
+            let _guard = set_now(now.parse().unwrap());
+            assert_eq!(AocDay::is_over(year), expected, "{now} {year}");
+        }
+    }
+
     #[test]
     fn url() {
         let day = |year, day| AocDay { year, day };
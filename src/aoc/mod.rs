@@ -1,4 +1,7 @@
+pub mod achievements;
 mod api;
 pub mod client;
 pub mod day;
 pub mod models;
+pub mod review;
+pub mod stats;
@@ -1,7 +1,6 @@
 use std::{cmp::Reverse, collections::HashMap};
 
 use chrono::{DateTime, Utc};
-use matrix_sdk::ruma::UserId;
 use serde::{Deserialize, Serialize};
 
 use crate::utils::{self, serde::via_string::ViaString};
@@ -22,6 +21,60 @@ pub struct PrivateLeaderboard {
     pub members: PrivateLeaderboardMembers,
 }
 
+impl PrivateLeaderboard {
+    /// Returns the number of local points the given member received for the
+    /// given star, or `None` if the member has not collected it yet.
+    pub fn star_score(
+        &self,
+        member: &PrivateLeaderboardMember,
+        day: u32,
+        part2: bool,
+    ) -> Option<u32> {
+        let ts = member.star_ts(day, part2)?;
+        let earlier = self
+            .members
+            .values()
+            .filter(|m| m.star_ts(day, part2).is_some_and(|t| t < ts))
+            .count();
+        Some((self.members.len() - earlier) as u32)
+    }
+
+    /// Returns a copy of the leaderboard with only the members for which
+    /// `include` returns `true`. Their local scores are recomputed as if the
+    /// other members had never joined the leaderboard.
+    pub fn filtered(&self, include: impl Fn(AocId) -> bool) -> Self {
+        let mut leaderboard = Self {
+            event: self.event.clone(),
+            owner_id: self.owner_id,
+            members: self
+                .members
+                .iter()
+                .filter(|(_, m)| include(m.id))
+                .map(|(id, m)| (*id, m.clone()))
+                .collect(),
+        };
+
+        let scores = leaderboard
+            .members
+            .iter()
+            .map(|(&id, member)| {
+                let score = member
+                    .completion_day_level
+                    .keys()
+                    .flat_map(|&day| [(day, false), (day, true)])
+                    .filter_map(|(day, part2)| leaderboard.star_score(member, day, part2))
+                    .sum();
+                (id, score)
+            })
+            .collect::<Vec<_>>();
+        for (id, score) in scores {
+            leaderboard.members.get_mut(&id).unwrap().local_score = score;
+        }
+
+        leaderboard
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PrivateLeaderboardMember {
     pub id: AocId,
@@ -42,17 +95,12 @@ impl PrivateLeaderboardMember {
         }
     }
 
-    pub fn matrix_mention_or_display_name(&self, matrix: Option<&UserId>) -> String {
-        match matrix {
-            Some(matrix) => format!("{} ({})", matrix.matrix_to_uri(), self.display_name()),
-            None => format!("**{}**", self.display_name()),
-        }
-    }
-
-    pub fn matrix_mention_or_display_name_html(&self, matrix: Option<&UserId>) -> String {
-        match matrix {
-            Some(matrix) => format!("{} ({})", matrix.matrix_to_uri(), self.display_name()),
-            None => format!("<b>{}</b>", self.display_name()),
+    pub fn star_ts(&self, day: u32, part2: bool) -> Option<DateTime<Utc>> {
+        let completion = self.completion_day_level.get(&day)?;
+        if part2 {
+            completion.snd.as_ref().map(|c| c.get_star_ts)
+        } else {
+            Some(completion.fst.get_star_ts)
         }
     }
 }
@@ -91,3 +139,93 @@ impl Ord for PrivateLeaderboardMember {
         key(self).cmp(&key(other))
     }
 }
+
+#[cfg(test)]
+pub mod testing {
+    use super::*;
+
+    /// Create a leaderboard member from a list of `(day, p1_ts, p2_ts)`.
+    pub fn member(id: AocId, days: &[(u32, i64, Option<i64>)]) -> PrivateLeaderboardMember {
+        let part = |ts| PrivateLeaderboardMemberCompletionDayPart {
+            get_star_ts: DateTime::from_timestamp(ts, 0).unwrap(),
+            star_index: 0,
+        };
+        PrivateLeaderboardMember {
+            id,
+            name: None,
+            global_score: 0,
+            local_score: 0,
+            stars: 0,
+            last_star_ts: Default::default(),
+            completion_day_level: days
+                .iter()
+                .map(|&(day, p1, p2)| {
+                    let completion = PrivateLeaderboardMemberCompletionDay {
+                        fst: part(p1),
+                        snd: p2.map(part),
+                    };
+                    (day, completion)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn leaderboard(members: &[PrivateLeaderboardMember]) -> PrivateLeaderboard {
+        PrivateLeaderboard {
+            event: "2024".into(),
+            owner_id: 1,
+            members: members
+                .iter()
+                .map(|m| (ViaString(m.id), m.clone()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::*;
+
+    #[test]
+    fn star_score() {
+        let members = [
+            member(1, &[(1, 100, Some(200))]),
+            member(2, &[(1, 50, Some(300))]),
+            member(3, &[(1, 100, None)]),
+            member(4, &[]),
+        ];
+        let leaderboard = leaderboard(&members);
+
+        let score = |i: usize, day, part2| leaderboard.star_score(&members[i], day, part2);
+        assert_eq!(score(0, 1, false), Some(3));
+        assert_eq!(score(1, 1, false), Some(4));
+        assert_eq!(score(2, 1, false), Some(3));
+        assert_eq!(score(3, 1, false), None);
+        assert_eq!(score(0, 1, true), Some(4));
+        assert_eq!(score(1, 1, true), Some(3));
+        assert_eq!(score(2, 1, true), None);
+        assert_eq!(score(0, 2, false), None);
+    }
+
+    #[test]
+    fn filtered() {
+        let members = [
+            member(1, &[(1, 100, Some(200))]),
+            member(2, &[(1, 50, Some(300))]),
+            member(3, &[(1, 150, Some(250)), (2, 100, None)]),
+        ];
+        let leaderboard = leaderboard(&members).filtered(|id| id != 2);
+
+        assert_eq!(leaderboard.members.len(), 2);
+        let score = |id| {
+            leaderboard
+                .members
+                .values()
+                .find(|m| m.id == id)
+                .unwrap()
+                .local_score
+        };
+        assert_eq!(score(1), 2 + 2);
+        assert_eq!(score(3), 1 + 1 + 2);
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    day::AocDay,
+    models::{AocId, PrivateLeaderboard},
+};
+use crate::{config::ReviewConfig, utils::fmt::fmt_timedelta};
+
+/// A solve pattern that looks automated and should be reviewed by an admin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuspiciousSolve {
+    pub member: AocId,
+    pub name: String,
+    pub year: i32,
+    pub reason: Suspicion,
+}
+
+impl SuspiciousSolve {
+    /// Whether both refer to the same solves, ignoring the display name and
+    /// the measured times. Bursts are the same if they share a day.
+    pub fn same_solves(&self, other: &Self) -> bool {
+        self.member == other.member
+            && self.year == other.year
+            && match (&self.reason, &other.reason) {
+                (Suspicion::FastPart1 { day: a, .. }, Suspicion::FastPart1 { day: b, .. })
+                | (Suspicion::FastPart2 { day: a, .. }, Suspicion::FastPart2 { day: b, .. }) => {
+                    a == b
+                }
+                (Suspicion::Burst { days: a, .. }, Suspicion::Burst { days: b, .. }) => {
+                    a.iter().any(|d| b.contains(d))
+                }
+                _ => false,
+            }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Suspicion {
+    /// Part one has been solved within `seconds` after the puzzle unlocked.
+    FastPart1 { day: u32, seconds: i64 },
+    /// Part two has been solved within `seconds` after part one.
+    FastPart2 { day: u32, seconds: i64 },
+    /// All of `days` have been completed within `seconds`.
+    Burst { days: Vec<u32>, seconds: i64 },
+}
+
+impl Suspicion {
+    pub fn description(&self) -> String {
+        match self {
+            Self::FastPart1 { day, seconds } => format!(
+                "Part one of day {day} solved {} after unlock",
+                fmt_timedelta(TimeDelta::seconds(*seconds))
+            ),
+            Self::FastPart2 { day, seconds } => format!(
+                "Part two of day {day} solved {} after part one",
+                fmt_timedelta(TimeDelta::seconds(*seconds))
+            ),
+            Self::Burst { days, seconds } => {
+                let mut out = String::from("Days ");
+                for (i, day) in days.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    write!(&mut out, "{day}").unwrap();
+                }
+                write!(
+                    &mut out,
+                    " completed within {}",
+                    fmt_timedelta(TimeDelta::seconds(*seconds))
+                )
+                .unwrap();
+                out
+            }
+        }
+    }
+}
+
+/// Find solves in the given leaderboard that look automated according to the
+/// thresholds in the review config.
+pub fn detect(
+    leaderboard: &PrivateLeaderboard,
+    year: i32,
+    config: &ReviewConfig,
+) -> Vec<SuspiciousSolve> {
+    let mut out = Vec::new();
+    for member in leaderboard.members.values() {
+        let mut push = |reason| {
+            out.push(SuspiciousSolve {
+                member: member.id,
+                name: member.display_name(),
+                year,
+                reason,
+            })
+        };
+
+        let mut days = member.completion_day_level.iter().collect::<Vec<_>>();
+        days.sort_unstable_by_key(|&(&day, _)| day);
+
+        let mut completions = Vec::new();
+        for (&day, completion) in days {
+            let unlock = AocDay { year, day }.unlock_datetime();
+            let p1 = completion.fst.get_star_ts;
+            let seconds = (p1 - unlock).num_seconds();
+            if seconds < config.min_part1_time as i64 {
+                push(Suspicion::FastPart1 { day, seconds });
+            }
+
+            if let Some(p2) = &completion.snd {
+                let seconds = (p2.get_star_ts - p1).num_seconds();
+                if seconds < config.min_part2_gap as i64 {
+                    push(Suspicion::FastPart2 { day, seconds });
+                }
+            }
+
+            let last = completion.snd.as_ref().map_or(p1, |p2| p2.get_star_ts);
+            completions.push((last, day));
+        }
+
+        completions.sort_unstable();
+        for (days, seconds) in bursts(&completions, config) {
+            push(Suspicion::Burst { days, seconds });
+        }
+    }
+
+    out.sort_by_key(|s| s.member);
+    out
+}
+
+/// Find maximal groups of at least `burst_days` days that have been completed
+/// within `burst_window` seconds.
+fn bursts(completions: &[(DateTime<Utc>, u32)], config: &ReviewConfig) -> Vec<(Vec<u32>, i64)> {
+    let window = TimeDelta::seconds(config.burst_window as i64);
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < completions.len() {
+        let start = completions[i].0;
+        let j = i + completions[i..]
+            .iter()
+            .take_while(|(ts, _)| *ts - start <= window)
+            .count();
+        if config.burst_days > 0 && j - i >= config.burst_days {
+            let mut days = completions[i..j]
+                .iter()
+                .map(|&(_, d)| d)
+                .collect::<Vec<_>>();
+            days.sort_unstable();
+            out.push((days, (completions[j - 1].0 - start).num_seconds()));
+            i = j;
+        } else {
+            i += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::models::testing::{leaderboard, member};
+
+    #[test]
+    fn detect() {
+        let config = ReviewConfig {
+            enabled: true,
+            interval: 300,
+            min_part1_time: 10,
+            min_part2_gap: 5,
+            burst_days: 3,
+            burst_window: 300,
+        };
+        let unlock = |day| AocDay { year: 2024, day }.unlock_datetime().timestamp();
+        let late = unlock(10);
+        let leaderboard = leaderboard(&[
+            member(
+                1,
+                &[
+                    (1, unlock(1) + 5, Some(unlock(1) + 60)),
+                    (2, unlock(2) + 600, Some(unlock(2) + 603)),
+                ],
+            ),
+            member(
+                2,
+                &[
+                    (1, late, Some(late + 60)),
+                    (2, late + 100, None),
+                    (3, late + 200, Some(late + 250)),
+                    (4, late + 5000, None),
+                ],
+            ),
+            member(3, &[(1, unlock(1) + 600, Some(unlock(1) + 900))]),
+        ]);
+
+        let reasons = super::detect(&leaderboard, 2024, &config)
+            .into_iter()
+            .map(|s| (s.member, s.reason))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reasons,
+            [
+                (1, Suspicion::FastPart1 { day: 1, seconds: 5 }),
+                (1, Suspicion::FastPart2 { day: 2, seconds: 3 }),
+                (
+                    2,
+                    Suspicion::Burst {
+                        days: vec![1, 2, 3],
+                        seconds: 190
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn same_solves() {
+        let solve = |name: &str, reason| SuspiciousSolve {
+            member: 1,
+            name: name.into(),
+            year: 2024,
+            reason,
+        };
+        let burst = |days: &[u32], seconds| Suspicion::Burst {
+            days: days.into(),
+            seconds,
+        };
+
+        let a = solve("Alice", Suspicion::FastPart1 { day: 1, seconds: 5 });
+        assert!(a.same_solves(&solve("Bob", Suspicion::FastPart1 { day: 1, seconds: 7 })));
+        assert!(!a.same_solves(&solve("Alice", Suspicion::FastPart2 { day: 1, seconds: 5 })));
+        assert!(!a.same_solves(&solve("Alice", Suspicion::FastPart1 { day: 2, seconds: 5 })));
+        assert!(!a.same_solves(&SuspiciousSolve {
+            year: 2023,
+            ..a.clone()
+        }));
+        assert!(!a.same_solves(&SuspiciousSolve {
+            member: 2,
+            ..a.clone()
+        }));
+
+        let b = solve("Alice", burst(&[1, 2, 3], 190));
+        assert!(b.same_solves(&solve("Alice", burst(&[2, 3, 4], 250))));
+        assert!(!b.same_solves(&solve("Alice", burst(&[4, 5, 6], 100))));
+    }
+}
@@ -0,0 +1,679 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{DateTime, FixedOffset, TimeDelta, TimeZone, Timelike, Utc};
+
+use super::{
+    day::AocDay,
+    models::{AocId, PrivateLeaderboard, PrivateLeaderboardMember},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStats {
+    pub day: AocDay,
+    pub p1: Option<SolveTimes>,
+    pub p2: Option<SolveTimes>,
+    pub gap_median: Option<TimeDelta>,
+    /// Number of part one and part two solves per hour of the day.
+    pub hours: [(usize, usize); 24],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolveTimes {
+    pub finishers: usize,
+    pub min: TimeDelta,
+    pub median: TimeDelta,
+    pub p90: TimeDelta,
+}
+
+impl DayStats {
+    pub fn compute(leaderboard: &PrivateLeaderboard, day: AocDay, timezone: FixedOffset) -> Self {
+        let unlock = day.unlock_datetime();
+        let hour = |ts| timezone.from_utc_datetime(&ts).hour() as usize;
+
+        let mut p1 = Vec::new();
+        let mut p2 = Vec::new();
+        let mut gaps = Vec::new();
+        let mut hours = [(0, 0); 24];
+        for member in leaderboard.members.values() {
+            let Some(ts1) = member.star_ts(day.day, false) else {
+                continue;
+            };
+            p1.push(ts1 - unlock);
+            hours[hour(ts1.naive_utc())].0 += 1;
+
+            if let Some(ts2) = member.star_ts(day.day, true) {
+                p2.push(ts2 - unlock);
+                gaps.push(ts2 - ts1);
+                hours[hour(ts2.naive_utc())].1 += 1;
+            }
+        }
+
+        gaps.sort_unstable();
+
+        Self {
+            day,
+            p1: SolveTimes::from_unsorted(p1),
+            p2: SolveTimes::from_unsorted(p2),
+            gap_median: percentile(&gaps, 50),
+            hours,
+        }
+    }
+
+    /// The median time it took to solve the whole puzzle (or only part one if
+    /// nobody has solved part two yet).
+    pub fn difficulty(&self) -> Option<TimeDelta> {
+        self.p2.or(self.p1).map(|t| t.median)
+    }
+}
+
+impl SolveTimes {
+    fn from_unsorted(mut times: Vec<TimeDelta>) -> Option<Self> {
+        times.sort_unstable();
+        Some(Self {
+            finishers: times.len(),
+            min: *times.first()?,
+            median: percentile(&times, 50)?,
+            p90: percentile(&times, 90)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberTotals {
+    pub id: AocId,
+    pub name: String,
+    pub years: Vec<i32>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub wins: Vec<i32>,
+    pub full_completions: Vec<i32>,
+}
+
+/// Assign competition ranks (1, 2, 2, 4, ...) to the given sorted items, where
+/// adjacent items with equal keys share a rank.
+pub fn competition_ranks<T, K: PartialEq>(
+    sorted: impl IntoIterator<Item = T>,
+    key: impl Fn(&T) -> K,
+) -> impl Iterator<Item = (usize, T)> {
+    let mut last_key = None;
+    let mut rank = 0;
+    sorted.into_iter().enumerate().map(move |(i, item)| {
+        let key = Some(key(&item));
+        if key != last_key {
+            last_key = key;
+            rank = i + 1;
+        }
+        (rank, item)
+    })
+}
+
+/// Returns the winner of the given leaderboard, if anybody has scored points.
+pub fn winner(leaderboard: &PrivateLeaderboard) -> Option<&PrivateLeaderboardMember> {
+    leaderboard
+        .members
+        .values()
+        .filter(|m| m.local_score > 0)
+        .min()
+}
+
+/// Aggregate the per-member statistics of the given leaderboards (ordered by
+/// year) over all years in which the member has collected at least one star.
+/// Members are grouped by the id returned by `canonical_id`, so multiple AoC
+/// accounts of the same person are merged into one entry.
+/// Wins are only counted for years whose event is over.
+pub fn all_time_totals<'a>(
+    leaderboards: impl IntoIterator<Item = (i32, &'a PrivateLeaderboard)>,
+    canonical_id: impl Fn(AocId) -> AocId,
+) -> Vec<MemberTotals> {
+    let mut totals = BTreeMap::new();
+    for (year, leaderboard) in leaderboards {
+        let winner = winner(leaderboard)
+            .filter(|_| AocDay::is_over(year))
+            .map(|m| m.id);
+        for member in leaderboard.members.values().filter(|m| m.stars > 0) {
+            let id = canonical_id(member.id);
+            let totals = totals.entry(id).or_insert_with(|| MemberTotals {
+                id,
+                name: String::new(),
+                years: Vec::new(),
+                stars: 0,
+                local_score: 0,
+                wins: Vec::new(),
+                full_completions: Vec::new(),
+            });
+            totals.name = member.display_name();
+            totals.stars += member.stars;
+            totals.local_score += member.local_score;
+            let push_year = |years: &mut Vec<i32>| {
+                if years.last() != Some(&year) {
+                    years.push(year);
+                }
+            };
+            push_year(&mut totals.years);
+            if winner == Some(member.id) {
+                push_year(&mut totals.wins);
+            }
+            if member.stars == 2 * AocDay::DAYS {
+                push_year(&mut totals.full_completions);
+            }
+        }
+    }
+    totals.into_values().collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeamScore<'a> {
+    pub name: &'a str,
+    /// Number of people in the team that have collected at least one star (on
+    /// the given day, if any). Multiple AoC accounts of the same person are
+    /// counted once.
+    pub members: usize,
+    pub score: u32,
+    pub stars: u32,
+}
+
+impl TeamScore<'_> {
+    pub fn average(&self) -> f64 {
+        self.score as f64 / self.members.max(1) as f64
+    }
+}
+
+/// Sum the local scores and stars of the members of each team, either over the
+/// whole year or only for the given day. `team` returns the name of the team a
+/// member belongs to and `canonical` the id representing all AoC accounts of
+/// the same person. Teams are ordered by name.
+pub fn team_scores<'a>(
+    leaderboard: &PrivateLeaderboard,
+    day: Option<u32>,
+    team: impl Fn(AocId) -> Option<&'a str>,
+    canonical: impl Fn(AocId) -> AocId,
+) -> Vec<TeamScore<'a>> {
+    let mut scores = BTreeMap::new();
+    let mut members = HashMap::<_, HashSet<_>>::new();
+    for member in leaderboard.members.values().filter(|m| m.stars > 0) {
+        let Some(name) = team(member.id) else {
+            continue;
+        };
+        let (score, stars) = match day {
+            Some(day) => [false, true]
+                .into_iter()
+                .filter_map(|part2| leaderboard.star_score(member, day, part2))
+                .fold((0, 0), |(score, stars), s| (score + s, stars + 1)),
+            None => (member.local_score, member.stars),
+        };
+        let entry = scores.entry(name).or_insert_with(|| TeamScore {
+            name,
+            members: 0,
+            score: 0,
+            stars: 0,
+        });
+        entry.score += score;
+        entry.stars += stars;
+        if stars > 0 {
+            members
+                .entry(name)
+                .or_default()
+                .insert(canonical(member.id));
+        }
+    }
+    scores
+        .into_values()
+        .map(|mut t| {
+            t.members = members.get(t.name).map_or(0, HashSet::len);
+            t
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Streak {
+    pub current: u32,
+    pub best: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Streaks {
+    /// Consecutive days on which part one has been solved.
+    pub solved: Streak,
+    /// Consecutive days on which part one has been solved on the same
+    /// (calendar) day the puzzle unlocked.
+    pub unlock_day: Streak,
+}
+
+impl Streaks {
+    /// Compute the streaks of the given member up to `last_day`. The current
+    /// streak may end with the day before `last_day`, as the last day might
+    /// still be solved.
+    pub fn compute(
+        member: &PrivateLeaderboardMember,
+        year: i32,
+        last_day: u32,
+        timezone: FixedOffset,
+    ) -> Self {
+        let date = |ts: DateTime<Utc>| timezone.from_utc_datetime(&ts.naive_utc()).date_naive();
+        let solved = |day| member.completion_day_level.contains_key(&day);
+        let unlock_day = |day| {
+            member.completion_day_level.get(&day).is_some_and(|c| {
+                date(c.fst.get_star_ts) == date(AocDay { year, day }.unlock_datetime())
+            })
+        };
+
+        Self {
+            solved: streak(last_day, solved),
+            unlock_day: streak(last_day, unlock_day),
+        }
+    }
+}
+
+fn streak(last_day: u32, solved: impl Fn(u32) -> bool) -> Streak {
+    let mut runs = vec![0];
+    for day in 1..=last_day {
+        runs.push(if solved(day) {
+            runs[runs.len() - 1] + 1
+        } else {
+            0
+        });
+    }
+    let best = runs.iter().copied().max().unwrap_or(0);
+    let current = match runs[..] {
+        [.., prev, 0] => prev,
+        [.., last] => last,
+        [] => 0,
+    };
+    Streak { current, best }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RankSnapshot {
+    pub rank: usize,
+    pub local_score: u32,
+}
+
+/// Recompute the rank and local score of every member after each of the first
+/// `days` days, i.e. at the time the following puzzle unlocked. Members
+/// without any stars at that time have no rank.
+pub fn rank_history(
+    leaderboard: &PrivateLeaderboard,
+    year: i32,
+    days: u32,
+) -> HashMap<AocId, Vec<Option<RankSnapshot>>> {
+    let stars = leaderboard
+        .members
+        .values()
+        .map(|member| {
+            let stars = member
+                .completion_day_level
+                .keys()
+                .flat_map(|&day| {
+                    [false, true].into_iter().filter_map(move |part2| {
+                        let ts = member.star_ts(day, part2)?;
+                        Some((ts, leaderboard.star_score(member, day, part2)?))
+                    })
+                })
+                .collect::<Vec<_>>();
+            (member.id, stars)
+        })
+        .collect::<Vec<_>>();
+
+    let mut history = stars
+        .iter()
+        .map(|&(id, _)| (id, Vec::with_capacity(days as usize)))
+        .collect::<HashMap<_, _>>();
+    for day in 1..=days {
+        let cutoff = AocDay { year, day: day + 1 }.unlock_datetime();
+        let scores = stars
+            .iter()
+            .map(|(id, stars)| {
+                let stars = stars.iter().filter(|&&(ts, _)| ts < cutoff);
+                let score = stars.clone().map(|&(_, s)| s).sum::<u32>();
+                (*id, score, stars.count())
+            })
+            .collect::<Vec<_>>();
+        for &(id, local_score, count) in &scores {
+            let snapshot = (count > 0).then(|| RankSnapshot {
+                rank: 1 + scores.iter().filter(|&&(_, s, _)| s > local_score).count(),
+                local_score,
+            });
+            history.get_mut(&id).unwrap().push(snapshot);
+        }
+    }
+    history
+}
+
+/// Nearest-rank percentile of a sorted slice.
+fn percentile(sorted: &[TimeDelta], p: usize) -> Option<TimeDelta> {
+    let rank = (sorted.len() * p).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressPoint {
+    pub ts: DateTime<Utc>,
+    pub stars: u32,
+    pub local_score: u32,
+}
+
+/// Returns the cumulative number of stars and local score of the given member
+/// after each of their stars, in chronological order.
+pub fn progress(
+    leaderboard: &PrivateLeaderboard,
+    member: &PrivateLeaderboardMember,
+) -> Vec<ProgressPoint> {
+    let mut stars = member
+        .completion_day_level
+        .keys()
+        .flat_map(|&day| {
+            [false, true].into_iter().filter_map(move |part2| {
+                let ts = member.star_ts(day, part2)?;
+                Some((ts, leaderboard.star_score(member, day, part2)?))
+            })
+        })
+        .collect::<Vec<_>>();
+    stars.sort_unstable();
+
+    stars
+        .into_iter()
+        .scan((0, 0), |(stars, local_score), (ts, score)| {
+            *stars += 1;
+            *local_score += score;
+            Some(ProgressPoint {
+                ts,
+                stars: *stars,
+                local_score: *local_score,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aoc::models::testing::{leaderboard, member},
+        utils::datetime::set_now,
+    };
+
+    #[test]
+    fn percentile() {
+        let times = (1..=10).map(TimeDelta::seconds).collect::<Vec<_>>();
+        assert_eq!(super::percentile(&times, 50), Some(TimeDelta::seconds(5)));
+        assert_eq!(super::percentile(&times, 90), Some(TimeDelta::seconds(9)));
+        assert_eq!(super::percentile(&times, 100), Some(TimeDelta::seconds(10)));
+        assert_eq!(
+            super::percentile(&times[..1], 50),
+            Some(TimeDelta::seconds(1))
+        );
+        assert_eq!(super::percentile(&[], 50), None);
+    }
+
+    #[test]
+    fn competition_ranks() {
+        let ranks = super::competition_ranks([5, 3, 3, 2, 2, 2, 1], |&x| x)
+            .map(|(rank, _)| rank)
+            .collect::<Vec<_>>();
+        assert_eq!(ranks, [1, 2, 2, 4, 4, 4, 7]);
+        assert_eq!(super::competition_ranks([0; 0], |&x| x).count(), 0);
+    }
+
+    #[test]
+    fn day_stats() {
+        let day = AocDay { year: 2024, day: 1 };
+        let unlock = day.unlock_datetime().timestamp();
+        let leaderboard = leaderboard(&[
+            member(1, &[(1, unlock + 60, Some(unlock + 120))]),
+            member(2, &[(1, unlock + 300, Some(unlock + 3900))]),
+            member(3, &[(1, unlock + 600, None)]),
+            member(4, &[(2, unlock + 86400 + 60, None)]),
+        ]);
+
+        let stats = DayStats::compute(&leaderboard, day, FixedOffset::east_opt(3600).unwrap());
+
+        let minutes = TimeDelta::minutes;
+        assert_eq!(
+            stats.p1,
+            Some(SolveTimes {
+                finishers: 3,
+                min: minutes(1),
+                median: minutes(5),
+                p90: minutes(10),
+            })
+        );
+        assert_eq!(
+            stats.p2,
+            Some(SolveTimes {
+                finishers: 2,
+                min: minutes(2),
+                median: minutes(2),
+                p90: minutes(65),
+            })
+        );
+        assert_eq!(stats.gap_median, Some(minutes(1)));
+        assert_eq!(stats.difficulty(), Some(minutes(2)));
+
+        let mut hours = [(0, 0); 24];
+        hours[6] = (3, 1);
+        hours[7] = (0, 1);
+        assert_eq!(stats.hours, hours);
+    }
+
+    #[test]
+    fn all_time_totals() {
+        let with_score = |mut m: PrivateLeaderboardMember, local_score, stars| {
+            m.local_score = local_score;
+            m.stars = stars;
+            m
+        };
+        let lb2022 = leaderboard(&[
+            with_score(member(1, &[]), 100, 50),
+            with_score(member(2, &[]), 120, 42),
+            with_score(member(3, &[]), 0, 0),
+        ]);
+        let lb2023 = leaderboard(&[
+            with_score(member(1, &[]), 80, 50),
+            with_score(member(3, &[]), 20, 4),
+        ]);
+
+        let _guard = set_now("2024-01-01T00:00:00Z".parse().unwrap());
+        assert_eq!(winner(&lb2022).map(|m| m.id), Some(2));
+        assert_eq!(winner(&leaderboard(&[member(1, &[])])), None);
+
+        let totals = super::all_time_totals([(2022, &lb2022), (2023, &lb2023)], |id| id);
+        let summary = totals
+            .iter()
+            .map(|t| {
+                (
+                    t.id,
+                    t.years.clone(),
+                    t.stars,
+                    t.local_score,
+                    t.wins.clone(),
+                    t.full_completions.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (1, vec![2022, 2023], 100, 180, vec![2023], vec![2022, 2023]),
+                (2, vec![2022], 42, 120, vec![2022], vec![]),
+                (3, vec![2023], 4, 20, vec![], vec![]),
+            ]
+        );
+
+        drop(_guard);
+        let _guard = set_now("2023-12-10T00:00:00Z".parse().unwrap());
+        let totals = super::all_time_totals([(2022, &lb2022), (2023, &lb2023)], |id| id);
+        let wins = totals.iter().map(|t| t.wins.clone()).collect::<Vec<_>>();
+        assert_eq!(wins, [vec![], vec![2022], vec![]]);
+
+        let merged = super::all_time_totals([(2022, &lb2022), (2023, &lb2023)], |id| id.min(2));
+        let summary = merged
+            .iter()
+            .map(|t| (t.id, t.years.clone(), t.stars, t.local_score))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (1, vec![2022, 2023], 100, 180),
+                (2, vec![2022, 2023], 46, 140)
+            ]
+        );
+    }
+
+    #[test]
+    fn team_scores() {
+        let with_score = |mut m: PrivateLeaderboardMember, local_score, stars| {
+            m.local_score = local_score;
+            m.stars = stars;
+            m
+        };
+        let unlock = AocDay { year: 2024, day: 1 }.unlock_datetime().timestamp();
+        let leaderboard = leaderboard(&[
+            with_score(member(1, &[(1, unlock + 60, Some(unlock + 120))]), 10, 2),
+            with_score(member(2, &[(1, unlock + 300, None)]), 5, 1),
+            with_score(member(3, &[(1, unlock + 30, Some(unlock + 600))]), 8, 2),
+            with_score(member(4, &[(1, unlock + 10, None)]), 4, 1),
+            with_score(member(5, &[]), 0, 0),
+            with_score(member(6, &[(2, unlock + 86400, None)]), 3, 1),
+        ]);
+        let team = |id| match id {
+            1 | 2 | 5 | 6 => Some("A"),
+            3 => Some("B"),
+            _ => None,
+        };
+        // 6 is an alt account of 2
+        let canonical = |id| if id == 6 { 2 } else { id };
+
+        let summary = |scores: Vec<TeamScore<'static>>| {
+            scores
+                .into_iter()
+                .map(|t| (t.name, t.members, t.score, t.stars))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            summary(super::team_scores(&leaderboard, None, team, canonical)),
+            [("A", 2, 18, 4), ("B", 1, 8, 2)]
+        );
+        assert_eq!(
+            summary(super::team_scores(&leaderboard, Some(1), team, canonical)),
+            [("A", 2, 13, 3), ("B", 1, 10, 2)]
+        );
+        assert_eq!(
+            summary(super::team_scores(&leaderboard, Some(2), team, canonical)),
+            [("A", 1, 6, 1), ("B", 0, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn streaks() {
+        let unlock = |day| AocDay { year: 2024, day }.unlock_datetime().timestamp();
+        let member = member(
+            1,
+            &[
+                (1, unlock(1) + 60, None),
+                (2, unlock(2) + 86400, None),
+                (3, unlock(3) + 60, None),
+                (5, unlock(5) + 60, None),
+                (6, unlock(6) + 60, None),
+                (7, unlock(7) + 60, None),
+                (8, unlock(8) + 60, None),
+            ],
+        );
+        let tz = FixedOffset::east_opt(3600).unwrap();
+
+        let streaks = Streaks::compute(&member, 2024, 8, tz);
+        assert_eq!(
+            streaks.solved,
+            Streak {
+                current: 4,
+                best: 4
+            }
+        );
+        assert_eq!(
+            streaks.unlock_day,
+            Streak {
+                current: 4,
+                best: 4
+            }
+        );
+
+        let streaks = Streaks::compute(&member, 2024, 9, tz);
+        assert_eq!(
+            streaks.solved,
+            Streak {
+                current: 4,
+                best: 4
+            }
+        );
+
+        let streaks = Streaks::compute(&member, 2024, 10, tz);
+        assert_eq!(
+            streaks.solved,
+            Streak {
+                current: 0,
+                best: 4
+            }
+        );
+
+        let streaks = Streaks::compute(&member, 2024, 3, tz);
+        assert_eq!(
+            streaks.solved,
+            Streak {
+                current: 3,
+                best: 3
+            }
+        );
+        assert_eq!(
+            streaks.unlock_day,
+            Streak {
+                current: 1,
+                best: 1
+            }
+        );
+    }
+
+    #[test]
+    fn rank_history() {
+        let unlock = |day| AocDay { year: 2024, day }.unlock_datetime().timestamp();
+        let leaderboard = leaderboard(&[
+            member(1, &[(1, unlock(1) + 60, Some(unlock(1) + 120))]),
+            member(
+                2,
+                &[
+                    (1, unlock(1) + 300, Some(unlock(1) + 360)),
+                    (2, unlock(2) + 60, Some(unlock(2) + 120)),
+                ],
+            ),
+            member(3, &[(1, unlock(2) + 600, None)]),
+        ]);
+
+        let history = super::rank_history(&leaderboard, 2024, 2);
+        let snapshot = |rank, local_score| Some(RankSnapshot { rank, local_score });
+        assert_eq!(history[&1], [snapshot(1, 6), snapshot(2, 6)]);
+        assert_eq!(history[&2], [snapshot(2, 4), snapshot(1, 10)]);
+        assert_eq!(history[&3], [None, snapshot(3, 1)]);
+    }
+
+    #[test]
+    fn progress() {
+        let members = [
+            member(1, &[(1, 100, Some(200)), (2, 150, None)]),
+            member(2, &[(1, 50, Some(300))]),
+        ];
+        let leaderboard = leaderboard(&members);
+
+        let point = |ts, stars, local_score| ProgressPoint {
+            ts: DateTime::from_timestamp(ts, 0).unwrap(),
+            stars,
+            local_score,
+        };
+        assert_eq!(
+            super::progress(&leaderboard, &members[0]),
+            [point(100, 1, 1), point(150, 2, 3), point(200, 3, 5)]
+        );
+        assert_eq!(
+            super::progress(&leaderboard, &members[1]),
+            [point(50, 1, 2), point(300, 2, 3)]
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use matrix_sdk::ruma::OwnedUserId;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::utils::store::Store;
+
+const BENCHMARKS_STORE_KEY: &[u8] = b"bench_runtimes";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchEntry {
+    pub user: OwnedUserId,
+    pub year: i32,
+    pub day: u32,
+    pub part2: bool,
+    pub lang: String,
+    pub runtime: Duration,
+    pub reported_at: DateTime<Utc>,
+}
+
+impl BenchEntry {
+    fn same_key(&self, other: &Self) -> bool {
+        (&self.user, self.year, self.day, self.part2)
+            == (&other.user, other.year, other.day, other.part2)
+            && self.lang.eq_ignore_ascii_case(&other.lang)
+    }
+}
+
+/// Solution runtimes reported by users.
+pub struct Benchmarks {
+    entries: RwLock<Vec<BenchEntry>>,
+    store: Store,
+}
+
+impl Benchmarks {
+    pub async fn new(store: Store) -> anyhow::Result<Self> {
+        let entries = store
+            .get::<Vec<BenchEntry>>(BENCHMARKS_STORE_KEY)
+            .await?
+            .unwrap_or_default();
+
+        Ok(Self {
+            entries: entries.into(),
+            store,
+        })
+    }
+
+    /// Store a reported runtime, replacing any previous report of the same user
+    /// for the same puzzle and language.
+    pub async fn report(&self, entry: BenchEntry) -> anyhow::Result<()> {
+        let mut guard = self.entries.write().await;
+        guard.retain(|e| !e.same_key(&entry));
+        guard.push(entry);
+        self.store
+            .set::<Vec<BenchEntry>>(BENCHMARKS_STORE_KEY, &guard)
+            .await
+    }
+
+    /// Returns the fastest runtime per day, part and language of the given
+    /// year, ordered by day, part and runtime.
+    pub async fn fastest(&self, year: i32) -> Vec<BenchEntry> {
+        fastest(self.entries.read().await.iter().filter(|e| e.year == year))
+    }
+}
+
+fn fastest<'a>(entries: impl Iterator<Item = &'a BenchEntry>) -> Vec<BenchEntry> {
+    let mut entries = entries.collect::<Vec<_>>();
+    entries.sort_by_key(|e| (e.day, e.part2, e.lang.to_lowercase(), e.runtime));
+    entries.dedup_by(|b, a| {
+        (a.day, a.part2) == (b.day, b.part2) && a.lang.eq_ignore_ascii_case(&b.lang)
+    });
+    let mut entries = entries.into_iter().cloned().collect::<Vec<_>>();
+    entries.sort_by_key(|e| (e.day, e.part2, e.runtime));
+    entries
+}
+
+/// Parse a runtime like `1.5ms`, `230us`, `230µs`, `12ns` or `2s`.
+pub fn parse_runtime(s: &str) -> Option<Duration> {
+    let idx = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = s.split_at(idx);
+    let value = value.parse::<f64>().ok()?;
+    let factor = match unit {
+        "ns" => 1e-9,
+        "us" | "µs" | "μs" => 1e-6,
+        "ms" => 1e-3,
+        "s" => 1.0,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(value * factor).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_runtime() {
+        for (input, expected) in [
+            ("12ns", Some(Duration::from_nanos(12))),
+            ("230us", Some(Duration::from_micros(230))),
+            ("230µs", Some(Duration::from_micros(230))),
+            ("1.5ms", Some(Duration::from_micros(1500))),
+            ("2s", Some(Duration::from_secs(2))),
+            ("0.25s", Some(Duration::from_millis(250))),
+            ("2", None),
+            ("ms", None),
+            ("1.5 ms", None),
+            ("3min", None),
+        ] {
+            assert_eq!(super::parse_runtime(input), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn fastest() {
+        let entry = |user: &str, day, part2, lang: &str, micros| BenchEntry {
+            user: user.try_into().unwrap(),
+            year: 2024,
+            day,
+            part2,
+            lang: lang.into(),
+            runtime: Duration::from_micros(micros),
+            reported_at: Default::default(),
+        };
+        let entries = [
+            entry("@a:example.com", 1, false, "Rust", 30),
+            entry("@b:example.com", 1, false, "rust", 20),
+            entry("@c:example.com", 1, false, "Python", 10),
+            entry("@a:example.com", 1, true, "Rust", 50),
+            entry("@b:example.com", 2, false, "Rust", 40),
+        ];
+
+        let fastest = super::fastest(entries.iter())
+            .into_iter()
+            .map(|e| (e.user.to_string(), e.day, e.part2, e.runtime.as_micros()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fastest,
+            [
+                ("@c:example.com".into(), 1, false, 10),
+                ("@b:example.com".into(), 1, false, 20),
+                ("@a:example.com".into(), 1, true, 50),
+                ("@b:example.com".into(), 2, false, 40),
+            ]
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+use tokio::sync::RwLock;
+
+use crate::{aoc::models::AocId, utils::store::Store};
+
+const COMPETITIVE_STORE_KEY: &[u8] = b"competitive_overrides";
+
+/// Overrides of the `competitive` flag from the users config, keyed by
+/// canonical AoC id.
+pub struct CompetitiveOverrides {
+    overrides: RwLock<HashMap<AocId, bool>>,
+    store: Store,
+}
+
+impl CompetitiveOverrides {
+    pub async fn new(store: Store) -> anyhow::Result<Self> {
+        let overrides = store
+            .get::<HashMap<AocId, bool>>(COMPETITIVE_STORE_KEY)
+            .await?
+            .unwrap_or_default();
+
+        Ok(Self {
+            overrides: overrides.into(),
+            store,
+        })
+    }
+
+    pub async fn get_all(&self) -> HashMap<AocId, bool> {
+        self.overrides.read().await.clone()
+    }
+
+    pub async fn set(&self, aoc: AocId, competitive: bool) -> anyhow::Result<()> {
+        let mut guard = self.overrides.write().await;
+        guard.insert(aoc, competitive);
+        self.store
+            .set::<HashMap<AocId, bool>>(COMPETITIVE_STORE_KEY, &guard)
+            .await
+    }
+}
@@ -1,14 +1,18 @@
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
 
 use chrono::FixedOffset;
 use config::{File, FileFormat};
 use matrix_sdk::ruma::{OwnedRoomId, OwnedUserId};
 use regex::Regex;
+use reqwest::Url;
 use serde::{Deserialize, Deserializer};
 
 use crate::{
     aoc::models::AocId,
-    utils::{self, regex_set_replacer::RegexSetReplacer},
+    utils::{self, regex_set_replacer::RegexSetReplacer, table::TableFormat},
 };
 
 pub fn load<'a>(config_path: impl Iterator<Item = &'a str>) -> anyhow::Result<Config> {
@@ -30,7 +34,35 @@ fn load_with_defaults<'a>(
         builder = builder.add_source(File::with_name(path.trim()));
     }
 
-    builder.build()?.try_deserialize().map_err(Into::into)
+    let config: Config = builder.build()?.try_deserialize()?;
+    config.validate()?;
+    Ok(config)
+}
+
+impl Config {
+    fn validate(&self) -> anyhow::Result<()> {
+        // members are identified by the first AoC account of their user, such
+        // that different accounts of one person can't end up in two teams
+        let mut teams = HashMap::new();
+        for team in &self.teams {
+            for &aoc in &team.members {
+                let id = self
+                    .users
+                    .iter()
+                    .find(|u| u.aoc.contains(&aoc))
+                    .and_then(|u| u.aoc.first().copied())
+                    .unwrap_or(aoc);
+                match teams.insert(id, &team.name) {
+                    Some(other) if other != &team.name => anyhow::bail!(
+                        "AoC account {aoc} is a member of both team '{other}' and team '{}'",
+                        team.name
+                    ),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -41,7 +73,12 @@ pub struct Config {
     pub matrix: MatrixConfig,
     pub aoc: AocConfig,
     pub garygrady: GarygradyConfig,
+    pub forge: ForgeConfig,
+    pub review: ReviewConfig,
+    pub first_blood: FirstBloodConfig,
     pub users: Vec<User>,
+    pub teams: Vec<Team>,
+    pub achievements: Vec<Achievement>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -53,6 +90,16 @@ pub struct MatrixConfig {
     pub room_id: OwnedRoomId,
     pub command_prefix: String,
     pub link_prefix: String,
+    /// Default rendering of tables in command responses.
+    pub table_format: TableFormat,
+    pub room_table_formats: Vec<RoomTableFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoomTableFormat {
+    pub room: OwnedRoomId,
+    pub format: TableFormat,
 }
 
 #[derive(Debug, Deserialize)]
@@ -62,6 +109,7 @@ pub struct AocConfig {
     pub leaderboard_rows: usize,
     pub default_cache_ttl: u64,
     pub cache_ttl_rules: Vec<CacheTtlRule>,
+    pub request_interval: u64,
     #[serde(deserialize_with = "deserialize_repo_rules")]
     pub repo_rules: RegexSetReplacer,
 }
@@ -80,12 +128,188 @@ pub struct GarygradyConfig {
     pub max_age: u64,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ForgeConfig {
+    pub enabled: bool,
+    pub interval: u64,
+    pub rules: Vec<ForgeRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReviewConfig {
+    pub enabled: bool,
+    pub interval: u64,
+    pub min_part1_time: u64,
+    pub min_part2_gap: u64,
+    pub burst_days: usize,
+    pub burst_window: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FirstBloodConfig {
+    pub enabled: bool,
+    /// Template of the announcement with the placeholders `{name}`, `{part}`,
+    /// `{puzzle}`, `{time}` and `{margin}`.
+    pub message: String,
+    /// Template of the `{margin}` placeholder with the placeholders `{second}`
+    /// and `{margin}`, used once the second solver is known.
+    pub margin_message: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ForgeRule {
+    #[serde(deserialize_with = "utils::serde::deserialize_regex")]
+    pub regex: Regex,
+    pub kind: ForgeKind,
+    pub api_url: Url,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForgeKind {
+    Github,
+    Gitlab,
+    /// Gitea and Forgejo
+    Gitea,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct User {
-    pub aoc: Option<AocId>,
+    #[serde(default, deserialize_with = "utils::serde::deserialize_one_or_many")]
+    pub aoc: Vec<AocId>,
     pub matrix: Option<OwnedUserId>,
-    pub repo: Option<String>,
+    #[serde(default)]
+    pub repo: Repos,
+    #[serde(default, deserialize_with = "utils::serde::deserialize_one_or_many")]
+    pub languages: Vec<String>,
+    /// Whether the user should be included in rankings.
+    #[serde(default = "default_true")]
+    pub competitive: bool,
+    /// Whether the user is mentioned (pinged) in solve and join notifications.
+    /// Otherwise they are only named.
+    #[serde(default = "default_true")]
+    pub ping: bool,
+    /// Rendering of tables in responses to commands sent by the user.
+    #[serde(default)]
+    pub table_format: Option<TableFormat>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Team {
+    pub name: String,
+    pub members: Vec<AocId>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub rule: AchievementRule,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum AchievementRule {
+    /// Be the first to complete any day.
+    FirstSolve,
+    /// Collect at least `count` stars.
+    Stars { count: u32 },
+    /// Complete at least `days` days within `seconds` after the puzzle
+    /// unlocked.
+    SolveWithin { days: usize, seconds: i64 },
+    /// Have the fastest gap between part one and part two of the year. Only
+    /// awarded once the event is over.
+    FastestGap,
+    /// Solve part one on `days` consecutive days.
+    Streak { days: u32 },
+}
+
+/// Solution repositories of a user, either a single url or a map of year to
+/// url (with an optional `default` entry for all other years).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Repos {
+    pub default: Option<String>,
+    pub by_year: BTreeMap<i32, String>,
+}
+
+impl Repos {
+    /// Returns the repository for the given year.
+    pub fn get(&self, year: i32) -> Option<&str> {
+        self.by_year
+            .get(&year)
+            .or(self.default.as_ref())
+            .map(|r| &**r)
+    }
+
+    /// Returns the default repository or the one of the most recent year.
+    pub fn latest(&self) -> Option<&str> {
+        self.default
+            .as_ref()
+            .or_else(|| self.by_year.values().next_back())
+            .map(|r| &**r)
+    }
+
+    /// Iterate over all distinct repositories.
+    pub fn all(&self) -> impl Iterator<Item = &str> {
+        let mut repos = self
+            .default
+            .iter()
+            .chain(self.by_year.values())
+            .map(|r| &**r)
+            .collect::<Vec<_>>();
+        repos.sort_unstable();
+        repos.dedup();
+        repos.into_iter()
+    }
+}
+
+impl<'de> Deserialize<'de> for Repos {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawRepos {
+            Single(String),
+            ByYear(HashMap<String, String>),
+        }
+
+        match RawRepos::deserialize(deserializer)? {
+            RawRepos::Single(repo) => Ok(Self {
+                default: Some(repo),
+                by_year: BTreeMap::new(),
+            }),
+            RawRepos::ByYear(repos) => {
+                let mut default = None;
+                let mut by_year = BTreeMap::new();
+                for (key, repo) in repos {
+                    if key == "default" {
+                        default = Some(repo);
+                    } else {
+                        let year = key.parse().map_err(|_| {
+                            serde::de::Error::custom(format!("Invalid repository year: {key}"))
+                        })?;
+                        by_year.insert(year, repo);
+                    }
+                }
+                Ok(Self { default, by_year })
+            }
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
 }
 
 fn deserialize_repo_rules<'de, D>(deserializer: D) -> Result<RegexSetReplacer, D::Error>
@@ -107,6 +331,34 @@ where
     Ok(RegexSetReplacer::new(rules))
 }
 
+#[cfg(test)]
+pub mod testing {
+    use super::*;
+
+    /// Load the default config with the given toml on top.
+    pub fn config(content: &str) -> Config {
+        let required = r#"
+            [matrix]
+            homeserver = "https://matrix.example.com"
+            store_path = ".store"
+            admin_ids = []
+            room_id = "!xoXcjSEJPUfQmzETtS:matrix.example.com"
+
+            [aoc]
+            session_file = ".session"
+        "#;
+        [include_str!("../config.toml"), required, content]
+            .into_iter()
+            .fold(config::Config::builder(), |builder, content| {
+                builder.add_source(File::from_str(content, FileFormat::Toml))
+            })
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,4 +378,118 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn team_members() {
+        let teams = |a, b| {
+            format!(
+                "users = [{{ aoc = [1, 2] }}]\n\
+                 teams = [{{ name = \"A\", members = {a} }}, {{ name = \"B\", members = {b} }}]"
+            )
+        };
+        assert!(testing::config(&teams("[1, 3]", "[4]")).validate().is_ok());
+        assert!(testing::config(&teams("[1, 2]", "[3]")).validate().is_ok());
+        assert!(testing::config(&teams("[3]", "[3]")).validate().is_err());
+        assert!(testing::config(&teams("[1]", "[2]")).validate().is_err());
+    }
+
+    #[test]
+    fn user_aoc_ids() {
+        let parse = |content| {
+            config::Config::builder()
+                .add_source(File::from_str(content, FileFormat::Toml))
+                .build()
+                .unwrap()
+                .try_deserialize::<User>()
+                .unwrap()
+                .aoc
+        };
+        assert_eq!(parse("aoc = 1"), [1]);
+        assert_eq!(parse("aoc = [2, 3]"), [2, 3]);
+        assert_eq!(parse("repo = \"https://example.com\""), [0; 0]);
+    }
+
+    #[test]
+    fn user_competitive() {
+        let parse = |content| {
+            config::Config::builder()
+                .add_source(File::from_str(content, FileFormat::Toml))
+                .build()
+                .unwrap()
+                .try_deserialize::<User>()
+                .unwrap()
+                .competitive
+        };
+        assert!(parse("aoc = 1"));
+        assert!(!parse("aoc = 1\ncompetitive = false"));
+    }
+
+    #[test]
+    fn achievement_rules() {
+        let parse = |content: &str| {
+            config::Config::builder()
+                .add_source(File::from_str(content, FileFormat::Toml))
+                .build()
+                .unwrap()
+                .try_deserialize::<Achievement>()
+                .map(|a| a.rule)
+                .ok()
+        };
+        let achievement =
+            |rule| format!("id = \"a\"\nname = \"A\"\ndescription = \"\"\nrule = {rule}");
+        assert_eq!(
+            parse(&achievement("{ kind = \"first_solve\" }")),
+            Some(AchievementRule::FirstSolve)
+        );
+        assert_eq!(
+            parse(&achievement(
+                "{ kind = \"solve_within\", days = 25, seconds = 3600 }"
+            )),
+            Some(AchievementRule::SolveWithin {
+                days: 25,
+                seconds: 3600
+            })
+        );
+        assert_eq!(parse(&achievement("{ kind = \"stars\" }")), None);
+        assert_eq!(parse(&achievement("{ kind = \"unknown\" }")), None);
+    }
+
+    #[test]
+    fn user_repos() {
+        let parse = |content| {
+            config::Config::builder()
+                .add_source(File::from_str(content, FileFormat::Toml))
+                .build()
+                .unwrap()
+                .try_deserialize::<User>()
+                .unwrap()
+                .repo
+        };
+
+        let repos = parse("aoc = 1");
+        assert_eq!(repos, Repos::default());
+        assert_eq!(repos.get(2024), None);
+        assert_eq!(repos.latest(), None);
+
+        let repos = parse("repo = \"https://example.com/aoc\"");
+        assert_eq!(repos.get(2024), Some("https://example.com/aoc"));
+        assert_eq!(repos.latest(), Some("https://example.com/aoc"));
+
+        let repos = parse(
+            "repo = { 2022 = \"https://example.com/aoc2022\", 2023 = \"https://example.com/aoc2023\" }",
+        );
+        assert_eq!(repos.get(2022), Some("https://example.com/aoc2022"));
+        assert_eq!(repos.get(2024), None);
+        assert_eq!(repos.latest(), Some("https://example.com/aoc2023"));
+
+        let repos = parse(
+            "repo = { default = \"https://example.com/aoc\", 2022 = \"https://example.com/aoc2022\" }",
+        );
+        assert_eq!(repos.get(2022), Some("https://example.com/aoc2022"));
+        assert_eq!(repos.get(2024), Some("https://example.com/aoc"));
+        assert_eq!(
+            repos.all().collect::<Vec<_>>(),
+            ["https://example.com/aoc", "https://example.com/aoc2022"]
+        );
+    }
 }
@@ -1,13 +1,24 @@
 use std::collections::HashMap;
 
-use matrix_sdk::{ruma::OwnedUserId, Room};
+use chrono::FixedOffset;
+use matrix_sdk::{
+    ruma::{OwnedUserId, RoomId, UserId},
+    Room,
+};
 use reqwest::Url;
 
 use crate::{
-    aoc::client::AocClient,
+    achievements::Achievements,
+    aoc::{client::AocClient, models::AocId},
+    bench::Benchmarks,
+    competitive::CompetitiveOverrides,
     config::{Config, User},
+    first_blood::FirstBloods,
+    forge::ForgeClient,
     mastodon,
-    utils::store::Store,
+    prefs::Preferences,
+    review::ReviewQueue,
+    utils::{store::Store, table::TableFormat},
 };
 
 pub struct Context {
@@ -16,12 +27,21 @@ pub struct Context {
     pub room: Room,
     pub aoc_client: AocClient,
     pub garygrady: ContextGarygrady,
+    pub forge: ForgeClient,
+    pub benchmarks: Benchmarks,
+    pub competitive: CompetitiveOverrides,
+    pub review: ReviewQueue,
+    pub achievements: Achievements,
+    pub first_bloods: FirstBloods,
+    pub prefs: Preferences,
     pub users: ContextUsers,
 }
 
 pub struct ContextUsers {
     pub by_aoc: HashMap<u64, User>,
     pub by_matrix: HashMap<OwnedUserId, User>,
+    canonical_aoc: HashMap<AocId, AocId>,
+    teams: HashMap<AocId, String>,
 }
 
 pub struct ContextGarygrady {
@@ -30,39 +50,163 @@ pub struct ContextGarygrady {
 }
 
 impl Context {
-    pub fn new(
+    pub async fn new(
         config: Config,
         store: Store,
         room: Room,
         aoc_client: AocClient,
         garygrady: ContextGarygrady,
-    ) -> Self {
+        forge: ForgeClient,
+    ) -> anyhow::Result<Self> {
         let users = ContextUsers::from_config(&config);
+        let benchmarks = Benchmarks::new(store.clone()).await?;
+        let competitive = CompetitiveOverrides::new(store.clone()).await?;
+        let review = ReviewQueue::new(store.clone()).await?;
+        let achievements = Achievements::new(store.clone()).await?;
+        let first_bloods = FirstBloods::new(store.clone()).await?;
+        let prefs = Preferences::new(store.clone()).await?;
 
-        Self {
+        Ok(Self {
             config,
             store,
             room,
             aoc_client,
             garygrady,
+            forge,
+            benchmarks,
+            competitive,
+            review,
+            achievements,
+            first_bloods,
+            prefs,
             users,
+        })
+    }
+
+    /// Returns a predicate that tells whether the given AoC account should be
+    /// included in rankings, either according to the users config or to an
+    /// override set via the `competitive` command.
+    pub async fn competitive(&self) -> impl Fn(AocId) -> bool + '_ {
+        let overrides = self.competitive.get_all().await;
+        move |aoc| {
+            overrides
+                .get(&self.users.canonical_aoc_id(aoc))
+                .copied()
+                .unwrap_or_else(|| self.users.by_aoc.get(&aoc).is_none_or(|u| u.competitive))
         }
     }
+
+    /// Returns how tables should be rendered for the given user in the given
+    /// room, unless overridden by a command argument.
+    pub async fn table_format(&self, room: &RoomId, user: &UserId) -> TableFormat {
+        self.prefs
+            .get(user)
+            .await
+            .format
+            .or_else(|| self.users.by_matrix.get(user).and_then(|u| u.table_format))
+            .or_else(|| {
+                self.config
+                    .matrix
+                    .room_table_formats
+                    .iter()
+                    .find(|r| r.room == room)
+                    .map(|r| r.format)
+            })
+            .unwrap_or(self.config.matrix.table_format)
+    }
+
+    /// Returns the timezone in which timestamps are shown to the given user.
+    pub async fn timezone(&self, user: &UserId) -> FixedOffset {
+        self.prefs
+            .get(user)
+            .await
+            .timezone
+            .map_or(self.config.local_timezone, |tz| tz.0)
+    }
+
+    /// Returns whether the given user wants to be mentioned (pinged) in
+    /// notifications.
+    pub async fn ping(&self, user: &UserId) -> bool {
+        self.prefs
+            .get(user)
+            .await
+            .ping
+            .or_else(|| self.users.by_matrix.get(user).map(|u| u.ping))
+            .unwrap_or(true)
+    }
+
+    /// Returns the (programming) languages of the solutions of the given user,
+    /// the one set in their preferences first.
+    pub async fn languages(&self, user: &User) -> Vec<String> {
+        let language = match &user.matrix {
+            Some(matrix) => self.prefs.get(matrix).await.language,
+            None => None,
+        };
+        language
+            .into_iter()
+            .chain(user.languages.iter().cloned())
+            .fold(Vec::new(), |mut languages, l| {
+                if !languages.contains(&l) {
+                    languages.push(l);
+                }
+                languages
+            })
+    }
 }
 
 impl ContextUsers {
-    fn from_config(config: &Config) -> Self {
+    pub fn from_config(config: &Config) -> Self {
         let by_aoc = config
             .users
             .iter()
-            .flat_map(|user| Some((user.aoc?, user.clone())))
+            .flat_map(|user| user.aoc.iter().map(|&aoc| (aoc, user.clone())))
             .collect();
         let by_matrix = config
             .users
             .iter()
             .flat_map(|user| Some((user.matrix.clone()?, user.clone())))
             .collect();
+        let canonical_aoc = config
+            .users
+            .iter()
+            .flat_map(|user| {
+                let &first = user.aoc.first()?;
+                Some(user.aoc.iter().map(move |&aoc| (aoc, first)))
+            })
+            .flatten()
+            .collect();
+        let teams = config
+            .teams
+            .iter()
+            .flat_map(|team| {
+                team.members.iter().flat_map(|aoc| {
+                    let ids = config
+                        .users
+                        .iter()
+                        .find(|u| u.aoc.contains(aoc))
+                        .map(|u| u.aoc.clone())
+                        .unwrap_or_else(|| vec![*aoc]);
+                    ids.into_iter().map(|id| (id, team.name.clone()))
+                })
+            })
+            .collect();
+
+        Self {
+            by_aoc,
+            by_matrix,
+            canonical_aoc,
+            teams,
+        }
+    }
+
+    /// Returns the AoC id that represents all AoC accounts of the person that
+    /// owns the given AoC account.
+    pub fn canonical_aoc_id(&self, aoc: AocId) -> AocId {
+        self.canonical_aoc.get(&aoc).copied().unwrap_or(aoc)
+    }
 
-        Self { by_aoc, by_matrix }
+    /// Returns the name of the team the given AoC account belongs to.
+    pub fn team(&self, aoc: AocId) -> Option<&str> {
+        self.teams.get(&aoc).map(|t| &**t)
     }
 }
@@ -0,0 +1,76 @@
+use matrix_sdk::ruma::OwnedEventId;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::{
+    aoc::models::AocId,
+    utils::{html::Html, store::Store},
+};
+
+const FIRST_BLOODS_STORE_KEY: &[u8] = b"first_bloods";
+
+/// The first solve of a puzzle part.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FirstBlood {
+    pub year: i32,
+    pub day: u32,
+    pub part2: bool,
+    pub member: AocId,
+    /// Rendered (html) name of the member.
+    pub name: Html,
+    /// Seconds since the puzzle unlocked.
+    pub time: i64,
+    /// Name of the second solver and their distance in seconds, once known.
+    pub second: Option<(String, i64)>,
+    /// The announcement, so it can be edited when the margin is known.
+    pub event_id: Option<OwnedEventId>,
+}
+
+pub struct FirstBloods {
+    records: RwLock<Vec<FirstBlood>>,
+    store: Store,
+}
+
+impl FirstBloods {
+    pub async fn new(store: Store) -> anyhow::Result<Self> {
+        let records = store
+            .get::<Vec<FirstBlood>>(FIRST_BLOODS_STORE_KEY)
+            .await?
+            .unwrap_or_default();
+
+        Ok(Self {
+            records: records.into(),
+            store,
+        })
+    }
+
+    pub async fn get(&self, year: i32, day: u32, part2: bool) -> Option<FirstBlood> {
+        self.records
+            .read()
+            .await
+            .iter()
+            .find(|r| (r.year, r.day, r.part2) == (year, day, part2))
+            .cloned()
+    }
+
+    pub async fn get_year(&self, year: i32) -> Vec<FirstBlood> {
+        self.records
+            .read()
+            .await
+            .iter()
+            .filter(|r| r.year == year)
+            .cloned()
+            .collect()
+    }
+
+    /// Insert the given record, replacing an existing record of the same
+    /// puzzle part.
+    pub async fn set(&self, record: FirstBlood) -> anyhow::Result<()> {
+        let mut guard = self.records.write().await;
+        guard.retain(|r| (r.year, r.day, r.part2) != (record.year, record.day, record.part2));
+        guard.push(record);
+        self.store
+            .set::<Vec<FirstBlood>>(FIRST_BLOODS_STORE_KEY, &guard)
+            .await
+    }
+}
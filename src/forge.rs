@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::trace;
+
+use crate::{
+    aoc::day::AocDay,
+    config::{ForgeKind, ForgeRule},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoMetadata {
+    pub language: Option<String>,
+    pub last_push: Option<DateTime<Utc>>,
+}
+
+impl RepoMetadata {
+    /// Returns whether the repository has not been pushed to since the puzzles
+    /// of the given year started to unlock.
+    pub fn is_stale(&self, year: i32) -> bool {
+        self.last_push
+            .is_some_and(|ts| ts < AocDay { year, day: 1 }.unlock_datetime())
+    }
+}
+
+pub struct ForgeClient {
+    http: Client,
+    rules: Vec<ForgeRule>,
+    metadata: RwLock<HashMap<String, RepoMetadata>>,
+}
+
+impl ForgeClient {
+    pub fn new(rules: Vec<ForgeRule>) -> anyhow::Result<Self> {
+        Ok(Self {
+            http: Client::builder().user_agent("aocbot").build()?,
+            rules,
+            metadata: Default::default(),
+        })
+    }
+
+    pub async fn get(&self, repo: &str) -> Option<RepoMetadata> {
+        self.metadata.read().await.get(repo).cloned()
+    }
+
+    /// Fetch the metadata of the given repository from the first matching
+    /// forge, or return `None` if no forge rule matches its url.
+    pub async fn fetch(&self, repo: &str) -> Option<anyhow::Result<RepoMetadata>> {
+        let (rule, captures) = self
+            .rules
+            .iter()
+            .find_map(|rule| Some((rule, rule.regex.captures(repo)?)))?;
+        let owner = captures.name("owner")?.as_str();
+        let name = captures.name("repo")?.as_str().trim_end_matches(".git");
+
+        trace!(repo, owner, name, kind = ?rule.kind, "fetching repository metadata");
+        let metadata = match rule.kind {
+            ForgeKind::Github => self.fetch_github(rule, owner, name).await,
+            ForgeKind::Gitlab => self.fetch_gitlab(rule, owner, name).await,
+            ForgeKind::Gitea => self.fetch_gitea(rule, owner, name).await,
+        };
+        Some(metadata)
+    }
+
+    pub async fn update(&self, metadata: HashMap<String, RepoMetadata>) {
+        *self.metadata.write().await = metadata;
+    }
+
+    fn get_request(&self, rule: &ForgeRule, url: Url) -> RequestBuilder {
+        let request = self.http.get(url);
+        match &rule.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn fetch_github(
+        &self,
+        rule: &ForgeRule,
+        owner: &str,
+        name: &str,
+    ) -> anyhow::Result<RepoMetadata> {
+        #[derive(Deserialize)]
+        struct Repository {
+            language: Option<String>,
+            pushed_at: Option<DateTime<Utc>>,
+        }
+
+        // https://docs.github.com/en/rest/repos/repos#get-a-repository
+        let repo = self
+            .get_request(rule, rule.api_url.join(&format!("repos/{owner}/{name}"))?)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Repository>()
+            .await?;
+
+        Ok(RepoMetadata {
+            language: repo.language,
+            last_push: repo.pushed_at,
+        })
+    }
+
+    async fn fetch_gitlab(
+        &self,
+        rule: &ForgeRule,
+        owner: &str,
+        name: &str,
+    ) -> anyhow::Result<RepoMetadata> {
+        #[derive(Deserialize)]
+        struct Project {
+            last_activity_at: Option<DateTime<Utc>>,
+        }
+
+        // https://docs.gitlab.com/ee/api/projects.html#get-single-project
+        let id = format!("{owner}/{name}").replace('/', "%2F");
+        let project = self
+            .get_request(rule, rule.api_url.join(&format!("projects/{id}"))?)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Project>()
+            .await?;
+
+        // https://docs.gitlab.com/ee/api/projects.html#languages
+        let languages = self
+            .get_request(
+                rule,
+                rule.api_url.join(&format!("projects/{id}/languages"))?,
+            )
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<HashMap<String, f64>>()
+            .await?;
+        let language = languages
+            .into_iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(lang, _)| lang);
+
+        Ok(RepoMetadata {
+            language,
+            last_push: project.last_activity_at,
+        })
+    }
+
+    async fn fetch_gitea(
+        &self,
+        rule: &ForgeRule,
+        owner: &str,
+        name: &str,
+    ) -> anyhow::Result<RepoMetadata> {
+        #[derive(Deserialize)]
+        struct Repository {
+            language: Option<String>,
+            updated_at: Option<DateTime<Utc>>,
+        }
+
+        // https://docs.gitea.com/api/1.22/#tag/repository/operation/repoGet
+        let repo = self
+            .get_request(rule, rule.api_url.join(&format!("repos/{owner}/{name}"))?)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Repository>()
+            .await?;
+
+        Ok(RepoMetadata {
+            language: repo.language.filter(|l| !l.is_empty()),
+            last_push: repo.updated_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    /// Start a local stand-in for a forge api that responds to requests for
+    /// the given paths with the given json bodies.
+    async fn serve(responses: &'static [(&'static str, &'static str)]) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0; 4096];
+                let n = stream.read(&mut buf).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let response = match responses.iter().find(|(p, _)| *p == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: \
+                         {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: \
+                             close\r\n\r\n"
+                        .into(),
+                };
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        format!("http://{addr}/api/").parse().unwrap()
+    }
+
+    fn client(kind: ForgeKind, api_url: Url) -> ForgeClient {
+        let rule = ForgeRule {
+            regex: Regex::new(r"^https://example\.com/(?<owner>[^/]+)/(?<repo>[^/]+)").unwrap(),
+            kind,
+            api_url,
+            token: None,
+        };
+        ForgeClient::new(vec![rule]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn github() {
+        let api = serve(&[(
+            "/api/repos/foo/aoc",
+            r#"{"language": "Rust", "pushed_at": "2024-12-03T12:00:00Z"}"#,
+        )])
+        .await;
+        let client = client(ForgeKind::Github, api);
+
+        let metadata = client.fetch("https://example.com/foo/aoc").await.unwrap();
+        assert_eq!(
+            metadata.unwrap(),
+            RepoMetadata {
+                language: Some("Rust".into()),
+                last_push: Some("2024-12-03T12:00:00Z".parse().unwrap()),
+            }
+        );
+        assert!(client
+            .fetch("https://example.com/foo/bar")
+            .await
+            .unwrap()
+            .is_err());
+        assert!(client.fetch("https://example.org/foo/aoc").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn gitlab() {
+        let api = serve(&[
+            (
+                "/api/projects/foo%2Faoc",
+                r#"{"last_activity_at": "2024-11-03T12:00:00Z"}"#,
+            ),
+            (
+                "/api/projects/foo%2Faoc/languages",
+                r#"{"Nix": 2.5, "Common Lisp": 97.5}"#,
+            ),
+        ])
+        .await;
+        let client = client(ForgeKind::Gitlab, api);
+
+        let metadata = client.fetch("https://example.com/foo/aoc").await.unwrap();
+        assert_eq!(
+            metadata.unwrap(),
+            RepoMetadata {
+                language: Some("Common Lisp".into()),
+                last_push: Some("2024-11-03T12:00:00Z".parse().unwrap()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn gitea() {
+        let api = serve(&[(
+            "/api/repos/foo/aoc",
+            r#"{"language": "", "updated_at": "2024-12-24T12:00:00Z"}"#,
+        )])
+        .await;
+        let client = client(ForgeKind::Gitea, api);
+
+        let metadata = client
+            .fetch("https://example.com/foo/aoc.git")
+            .await
+            .unwrap();
+        assert_eq!(
+            metadata.unwrap(),
+            RepoMetadata {
+                language: None,
+                last_push: Some("2024-12-24T12:00:00Z".parse().unwrap()),
+            }
+        );
+    }
+
+    #[test]
+    fn is_stale() {
+        let metadata = |ts: &str| RepoMetadata {
+            language: None,
+            last_push: Some(ts.parse().unwrap()),
+        };
+        assert!(metadata("2024-11-30T12:00:00Z").is_stale(2024));
+        assert!(!metadata("2024-12-01T05:00:00Z").is_stale(2024));
+        assert!(!metadata("2024-11-30T12:00:00Z").is_stale(2023));
+    }
+}
@@ -15,15 +15,23 @@ use tracing::{error, info};
 use crate::{
     aoc::client::AocClient,
     context::{Context, ContextGarygrady},
+    forge::ForgeClient,
     matrix::create_client,
     utils::store::Store,
 };
 
+mod achievements;
 mod aoc;
+mod bench;
+mod competitive;
 mod config;
 mod context;
+mod first_blood;
+mod forge;
 mod mastodon;
 mod matrix;
+mod prefs;
+mod review;
 mod tasks;
 mod utils;
 
@@ -146,6 +154,7 @@ pub async fn run(config_path: impl Iterator<Item = &str>) -> anyhow::Result<()>
             .iter()
             .map(|r| (r.minutes_after_unlock, Duration::from_secs(r.ttl)))
             .collect(),
+        Duration::from_secs(config.aoc.request_interval),
         store.clone(),
     )
     .await?;
@@ -165,6 +174,9 @@ pub async fn run(config_path: impl Iterator<Item = &str>) -> anyhow::Result<()>
         user_id: garygrady_id,
     };
 
+    // Forge setup
+    let forge = ForgeClient::new(config.forge.rules.clone())?;
+
     // Setup matrix bot
     let bot = matrix::Bot::setup(client.clone()).await?;
 
@@ -178,7 +190,7 @@ pub async fn run(config_path: impl Iterator<Item = &str>) -> anyhow::Result<()>
         room.join().await?;
     }
 
-    let context = Arc::new(Context::new(config, store, room, aoc_client, garygrady));
+    let context = Arc::new(Context::new(config, store, room, aoc_client, garygrady, forge).await?);
 
     tasks::start(Arc::clone(&context));
 
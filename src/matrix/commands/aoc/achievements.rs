@@ -0,0 +1,80 @@
+use matrix_sdk::{ruma::events::room::message::OriginalRoomMessageEvent, Room};
+
+use crate::{
+    aoc::day::AocDay,
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{html_message, RoomExt},
+    },
+    utils::{
+        html::html,
+        table::{Cell, Table},
+    },
+};
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let most_recent_year = AocDay::most_recent().year;
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
+        y.parse()
+            .ok()
+            .filter(|y| (2015..=most_recent_year).contains(y))
+    }) {
+        Some(Some(y)) => y,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
+        None => most_recent_year,
+    };
+
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let (leaderboard, _) = context.aoc_client.get_private_leaderboard(year).await?;
+    let unlocked = context.achievements.get_all().await;
+
+    let mut table = Table::new(["Achievement", "Description", "Unlocked By"]);
+    for achievement in &context.config.achievements {
+        let mut members = unlocked
+            .iter()
+            .filter(|(_, a)| a.iter().any(|a| a.id == achievement.id && a.year == year))
+            .map(|(&id, _)| {
+                leaderboard
+                    .members
+                    .values()
+                    .find(|m| context.users.canonical_aoc_id(m.id) == id)
+                    .map(|m| m.display_name())
+                    .unwrap_or_else(|| format!("[anonymous user #{id}]"))
+            })
+            .collect::<Vec<_>>();
+        members.sort_unstable();
+
+        table.push([
+            Cell::new(format!("🏆 {}", achievement.name)).bold(true),
+            Cell::new(&achievement.description),
+            Cell::new(members.join(", ")),
+        ]);
+    }
+
+    let out = html!(
+        r#"
+<h3>Achievements (Advent of Code {year})</h3>
+{table}
+"#,
+        year,
+        table = table.render(format)
+    );
+
+    room.reply_to(event, html_message(out)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use matrix_sdk::{
+    ruma::events::{
+        reaction::ReactionEventContent, relation::Annotation,
+        room::message::OriginalRoomMessageEvent,
+    },
+    Room,
+};
+
+use crate::{
+    aoc::day::AocDay,
+    bench::{parse_runtime, BenchEntry},
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{html_message, room_display_name, user_pill, RoomExt},
+    },
+    utils::{
+        datetime::now,
+        fmt::fmt_runtime,
+        html::html,
+        table::{Cell, Table},
+    },
+};
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    if cmd.args.front() == Some(&"board") {
+        cmd.args.pop_front();
+        board(event, room, context, cmd).await
+    } else {
+        report(event, room, context, cmd).await
+    }
+}
+
+async fn report(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let day = match cmd
+        .get_from_kwargs_or_args("day")
+        .map(|d| d.parse::<u32>().ok().filter(|d| (1..=25).contains(d)))
+    {
+        Some(Some(d)) => d,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'day'").await,
+        None => return send_error(&room, event, "Argument 'day' is required").await,
+    };
+
+    let part2 = match cmd.get_from_kwargs_or_args("part") {
+        Some("1") => false,
+        Some("2") => true,
+        Some(_) => return send_error(&room, event, "Failed to parse argument 'part'").await,
+        None => return send_error(&room, event, "Argument 'part' is required").await,
+    };
+
+    let runtime = match cmd.get_from_kwargs_or_args("duration").map(parse_runtime) {
+        Some(Some(x)) => x,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'duration'").await,
+        None => return send_error(&room, event, "Argument 'duration' is required").await,
+    };
+
+    let lang = match cmd.get_from_kwargs_or_args("lang") {
+        Some(lang) => Some(lang.to_owned()),
+        None => context.prefs.get(&event.sender).await.language.or_else(|| {
+            let user = context.users.by_matrix.get(&event.sender)?;
+            user.languages.first().cloned()
+        }),
+    };
+    let Some(lang) = lang else {
+        return send_error(&room, event, "Argument 'lang' is required").await;
+    };
+
+    let most_recent_year = AocDay::most_recent().year;
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
+        y.parse()
+            .ok()
+            .filter(|y| (2015..=most_recent_year).contains(y))
+    }) {
+        Some(Some(y)) => y,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
+        None => most_recent_year,
+    };
+
+    context
+        .benchmarks
+        .report(BenchEntry {
+            user: event.sender.clone(),
+            year,
+            day,
+            part2,
+            lang,
+            runtime,
+            reported_at: now(),
+        })
+        .await?;
+
+    room.send(ReactionEventContent::new(Annotation::new(
+        event.event_id.clone(),
+        "✅️".into(),
+    )))
+    .await?;
+
+    Ok(())
+}
+
+async fn board(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let day = match cmd
+        .get_from_kwargs_or_args("day")
+        .map(|d| d.parse::<u32>().ok().filter(|d| (1..=25).contains(d)))
+    {
+        Some(Some(d)) => Some(d),
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'day'").await,
+        None => None,
+    };
+
+    let most_recent_year = AocDay::most_recent().year;
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
+        y.parse()
+            .ok()
+            .filter(|y| (2015..=most_recent_year).contains(y))
+    }) {
+        Some(Some(y)) => y,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
+        None => most_recent_year,
+    };
+
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let mut entries = context.benchmarks.fastest(year).await;
+    if let Some(day) = day {
+        entries.retain(|e| e.day == day);
+    }
+
+    let title = match day {
+        Some(day) => format!("{year}/{day:02}"),
+        None => year.to_string(),
+    };
+    let mut table = Table::new(["Day", "Part", "Language", "Runtime", "User"]);
+    for BenchEntry {
+        user,
+        day,
+        part2,
+        lang,
+        runtime,
+        ..
+    } in entries
+    {
+        let name = room_display_name(&room, &user).await;
+        table.push([
+            Cell::new(day),
+            Cell::new(if part2 { 2 } else { 1 }),
+            Cell::new(lang),
+            Cell::new(fmt_runtime(runtime)).bold(true),
+            Cell::html(user_pill(&user, &name), name),
+        ]);
+    }
+
+    let out = html!(
+        r#"
+<h3>Fastest Runtimes (Advent of Code {title})</h3>
+{table}
+"#,
+        title,
+        table = table.render(format)
+    );
+
+    room.reply_to(event, html_message(out)).await?;
+
+    Ok(())
+}
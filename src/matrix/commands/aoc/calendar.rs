@@ -0,0 +1,283 @@
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use matrix_sdk::{
+    ruma::{api::client::error::ErrorKind, events::room::message::OriginalRoomMessageEvent},
+    Room,
+};
+
+use super::leaderboard::reply_fetch_error;
+use crate::{
+    aoc::{
+        day::AocDay,
+        models::{AocId, PrivateLeaderboard, PrivateLeaderboardMember},
+        stats::competition_ranks,
+    },
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{error_message, html_message, RoomExt},
+    },
+    utils::{
+        datetime::DateTimeExt,
+        fmt::fmt_rank,
+        html::{html, Html},
+        table::{Cell, Table, TableFormat},
+    },
+};
+
+/// The colors of the stars for days with both parts, only part one and no part
+/// solved.
+const GOLD_STAR: &str = "#ffff66";
+const SILVER_STAR: &str = "#9999cc";
+const NO_STAR: &str = "#333340";
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let most_recent = AocDay::most_recent();
+    let most_recent_year = most_recent.year;
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
+        y.parse()
+            .ok()
+            .filter(|y| (2015..=most_recent_year).contains(y))
+    }) {
+        Some(Some(y)) => y,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
+        None => most_recent_year,
+    };
+
+    let rows = match cmd
+        .get_from_kwargs_or_args("rows")
+        .map(|y| y.parse().ok().filter(|x| (0..=200).contains(x)))
+    {
+        Some(Some(x)) => x,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'rows'").await,
+        None => context.config.aoc.leaderboard_rows,
+    };
+
+    let offset = match cmd
+        .get_from_kwargs_or_args("offset")
+        .map(|y| y.parse().ok().filter(|x| (0..=200).contains(x)))
+    {
+        Some(Some(x)) => x,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'offset'").await,
+        None => 0,
+    };
+
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let result = context.aoc_client.get_private_leaderboard(year).await;
+    let Some((leaderboard, last_update)) = reply_fetch_error(&room, event, year, result).await?
+    else {
+        return Ok(());
+    };
+    let days = if year == most_recent.year {
+        most_recent.day
+    } else {
+        25
+    };
+
+    let competitive = context.competitive().await;
+    let view = view(&leaderboard, competitive, year, days, rows, offset);
+    let timezone = context.timezone(&event.sender).await;
+    let calendar = render(&view, format, timezone, last_update);
+
+    if let Err(err) = room.reply_to(event, html_message(calendar)).await {
+        if err
+            .as_client_api_error()
+            .and_then(|err| err.error_kind())
+            .is_some_and(|kind| matches!(kind, ErrorKind::TooLarge))
+        {
+            room.reply_to(
+                event,
+                error_message(
+                    "The requested calendar slice would be too large to fit in a matrix \
+                     message. Try to reduce the number of rows.",
+                ),
+            )
+            .await?;
+            return Ok(());
+        } else {
+            return Err(err.into());
+        }
+    }
+
+    Ok(())
+}
+
+pub struct CalendarView {
+    pub year: i32,
+    pub days: u32,
+    pub rows: Vec<CalendarRow>,
+    /// Members excluded from the ranking, only populated if the requested
+    /// slice includes the end of the leaderboard.
+    pub non_competitive: Vec<CalendarRow>,
+}
+
+pub struct CalendarRow {
+    /// The rank of the member, `None` for non-competitive members.
+    pub rank: Option<usize>,
+    pub name: String,
+    pub stars: u32,
+    /// The number of stars collected on each of the first `days` days.
+    pub days: Vec<u8>,
+}
+
+/// Rank the competitive members of the given leaderboard like the leaderboard
+/// command and select the requested slice.
+pub fn view(
+    leaderboard: &PrivateLeaderboard,
+    competitive: impl Fn(AocId) -> bool,
+    year: i32,
+    days: u32,
+    rows: usize,
+    offset: usize,
+) -> CalendarView {
+    let ranked = leaderboard.filtered(&competitive);
+    let mut members = ranked.members.values().collect::<Vec<_>>();
+    let mut non_competitive = leaderboard
+        .members
+        .values()
+        .filter(|m| !competitive(m.id) && m.stars > 0)
+        .collect::<Vec<_>>();
+    members.sort_unstable();
+    non_competitive.sort_unstable();
+    if offset + rows < members.len() {
+        non_competitive.clear();
+    }
+
+    let row = |rank, member: &PrivateLeaderboardMember| CalendarRow {
+        rank,
+        name: member.display_name(),
+        stars: member.stars,
+        days: (1..=days)
+            .map(|d| match member.completion_day_level.get(&d) {
+                Some(c) if c.snd.is_some() => 2,
+                Some(_) => 1,
+                None => 0,
+            })
+            .collect(),
+    };
+
+    let rows = competition_ranks(members, |m| m.local_score)
+        .skip(offset)
+        .take(rows)
+        .map(|(rank, member)| row(Some(rank), member))
+        .collect();
+
+    CalendarView {
+        year,
+        days,
+        rows,
+        non_competitive: non_competitive.into_iter().map(|m| row(None, m)).collect(),
+    }
+}
+
+fn render(
+    view: &CalendarView,
+    format: TableFormat,
+    timezone: FixedOffset,
+    last_update: DateTime<Utc>,
+) -> Html {
+    let header = |rank: bool| {
+        rank.then(|| "Rank".to_owned())
+            .into_iter()
+            .chain(["AoC Name".to_owned()])
+            .chain((1..=view.days).map(|d| d.to_string()))
+            .chain(["Stars".to_owned()])
+    };
+    let cells = |row: &CalendarRow| {
+        let stars = row.days.iter().map(|&stars| {
+            let (color, star) = match stars {
+                2 => (GOLD_STAR, '★'),
+                1 => (SILVER_STAR, '☆'),
+                _ => (NO_STAR, '·'),
+            };
+            Cell::html(
+                html!(r#"<span data-mx-color="{color}">★</span>"#, color),
+                star,
+            )
+        });
+        row.rank
+            .map(|rank| Cell::new(fmt_rank(rank)))
+            .into_iter()
+            .chain([Cell::new(&row.name)])
+            .chain(stars)
+            .chain([Cell::new(row.stars)])
+            .collect::<Vec<_>>()
+    };
+
+    let mut table = Table::new(header(true));
+    for row in &view.rows {
+        table.push(cells(row));
+    }
+
+    let mut calendar = html!(
+        r#"
+<h3>Star Calendar (Advent of Code {year})</h3>
+{table}
+"#,
+        year = view.year,
+        table = table.render(format)
+    );
+
+    if !view.non_competitive.is_empty() {
+        let mut table = Table::new(header(false));
+        for row in &view.non_competitive {
+            table.push(cells(row));
+        }
+        calendar.push(html!(
+            r#"
+<h4>Non-Competitive</h4>
+{table}
+"#,
+            table = table.render(format)
+        ));
+    }
+
+    let last_update = timezone
+        .from_utc_datetime(&last_update.naive_utc())
+        .format_ymd_hms_z();
+    calendar.push(html!(
+        r#"
+<sup>Last update: {last_update}</sup>
+"#,
+        last_update
+    ));
+
+    calendar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        context::ContextUsers,
+        matrix::commands::aoc::testing::{config, last_update, leaderboard},
+    };
+
+    #[test]
+    fn snapshot() {
+        let config = config();
+        let users = ContextUsers::from_config(&config);
+        let competitive = |id| users.by_aoc.get(&id).is_none_or(|u| u.competitive);
+
+        let v = super::view(&leaderboard(), competitive, 2024, 3, 20, 0);
+        assert_eq!(v.rows[1].days, [2, 1, 0]);
+        for format in [TableFormat::Table, TableFormat::Compact] {
+            insta::assert_snapshot!(
+                format!("{format:?}").to_lowercase(),
+                render(&v, format, config.local_timezone, last_update()).as_str()
+            );
+        }
+    }
+}
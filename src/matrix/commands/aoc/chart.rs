@@ -0,0 +1,180 @@
+use chrono::{DateTime, Utc};
+use matrix_sdk::{
+    ruma::{
+        events::{
+            room::{
+                message::{
+                    ImageMessageEventContent, MessageType, OriginalRoomMessageEvent,
+                    RoomMessageEventContent,
+                },
+                ImageInfo,
+            },
+            Mentions,
+        },
+        UInt,
+    },
+    Room,
+};
+
+use crate::{
+    aoc::{day::AocDay, stats::progress},
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::RoomExt,
+    },
+    utils::{
+        chart::{self, Series},
+        datetime::now,
+    },
+};
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Score,
+    Stars,
+    Times,
+}
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let most_recent = AocDay::most_recent();
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
+        y.parse()
+            .ok()
+            .filter(|y| (2015..=most_recent.year).contains(y))
+    }) {
+        Some(Some(y)) => y,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
+        None => most_recent.year,
+    };
+
+    let top = match cmd
+        .get_from_kwargs_or_args("top")
+        .map(|n| n.parse().ok().filter(|n| (1..=20).contains(n)))
+    {
+        Some(Some(n)) => n,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'top'").await,
+        None => 10,
+    };
+
+    let mode = match cmd.get_from_kwargs_or_args("mode") {
+        Some("score") | None => Mode::Score,
+        Some("stars") => Mode::Stars,
+        Some("times") => Mode::Times,
+        Some(_) => return send_error(&room, event, "Failed to parse argument 'mode'").await,
+    };
+
+    let (leaderboard, _) = context.aoc_client.get_private_leaderboard(year).await?;
+
+    let leaderboard = leaderboard.filtered(context.competitive().await);
+    let mut members = leaderboard
+        .members
+        .values()
+        .filter(|m| m.stars > 0)
+        .collect::<Vec<_>>();
+    members.sort_unstable();
+    members.truncate(top);
+
+    let days = if year == most_recent.year {
+        most_recent.day
+    } else {
+        25
+    };
+    let start = AocDay { year, day: 1 }.unlock_datetime();
+    let end = AocDay {
+        year,
+        day: days + 1,
+    }
+    .unlock_datetime()
+    .min(now());
+    // fractional days since the first puzzle unlocked, starting at 1
+    let x = |ts: DateTime<Utc>| 1.0 + (ts - start).num_seconds() as f64 / 86400.0;
+
+    let x_end = x(end);
+
+    let series = members
+        .iter()
+        .map(|&member| {
+            let points = match mode {
+                Mode::Score | Mode::Stars => {
+                    let mut points = vec![(1.0, 0.0)];
+                    let mut last = 0.0;
+                    for p in progress(&leaderboard, member) {
+                        let y = match mode {
+                            Mode::Stars => p.stars,
+                            _ => p.local_score,
+                        } as f64;
+                        points.extend([(x(p.ts), last), (x(p.ts), y)]);
+                        last = y;
+                    }
+                    points.push((x_end, last));
+                    points
+                }
+                Mode::Times => (1..=days)
+                    .filter_map(|day| {
+                        let ts = member.star_ts(day, true)?;
+                        let unlock = AocDay { year, day }.unlock_datetime();
+                        Some((day as f64, (ts - unlock).num_seconds() as f64 / 60.0))
+                    })
+                    .collect(),
+            };
+            Series {
+                name: member.display_name(),
+                points,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let (title, filename) = match mode {
+        Mode::Score => (format!("Local Score (Advent of Code {year})"), "score"),
+        Mode::Stars => (format!("Stars (Advent of Code {year})"), "stars"),
+        Mode::Times => (format!("Solve Times (Advent of Code {year})"), "times"),
+    };
+    let filename = format!("aoc-{year}-{filename}.png");
+
+    let png = tokio::task::spawn_blocking({
+        let title = title.clone();
+        move || match mode {
+            Mode::Score => chart::line_chart(&title, "Day", "Local Score", 1.0..x_end, &series),
+            Mode::Stars => chart::line_chart(&title, "Day", "Stars", 1.0..x_end, &series),
+            Mode::Times => chart::scatter_chart(
+                &title,
+                "Day",
+                "Minutes until Part 2",
+                0.5..days as f64 + 0.5,
+                &series,
+            ),
+        }
+    })
+    .await??;
+
+    let mut info = ImageInfo::new();
+    info.mimetype = Some(mime_guess::mime::IMAGE_PNG.to_string());
+    info.width = Some(chart::WIDTH.into());
+    info.height = Some(chart::HEIGHT.into());
+    info.size = UInt::new(png.len() as _);
+
+    let response = room
+        .client()
+        .media()
+        .upload(&mime_guess::mime::IMAGE_PNG, png, None)
+        .await?;
+
+    let mut image_message = ImageMessageEventContent::plain(title, response.content_uri);
+    image_message.filename = Some(filename);
+    image_message.info = Some(info.into());
+
+    room.reply_to(
+        event,
+        RoomMessageEventContent::new(MessageType::Image(image_message))
+            .add_mentions(Mentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
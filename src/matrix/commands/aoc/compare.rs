@@ -0,0 +1,249 @@
+use std::cmp::Ordering;
+
+use chrono::{DateTime, FixedOffset, TimeDelta, TimeZone, Utc};
+use matrix_sdk::{ruma::events::room::message::OriginalRoomMessageEvent, Room};
+
+use super::user::find_member;
+use crate::{
+    aoc::{
+        day::AocDay,
+        models::{PrivateLeaderboard, PrivateLeaderboardMember},
+    },
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{find_pill, html_error_message, html_message, RoomExt},
+    },
+    utils::{
+        datetime::DateTimeExt,
+        fmt::fmt_timedelta,
+        html::{html, Html},
+        table::{Cell, Table, TableFormat},
+    },
+};
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let most_recent = AocDay::most_recent();
+
+    let Some(user1) = cmd.get_from_kwargs_or_args("user1") else {
+        return send_error(&room, event, "Argument 'user1' is required").await;
+    };
+    let Some(user2) = cmd.get_from_kwargs_or_args("user2") else {
+        return send_error(&room, event, "Argument 'user2' is required").await;
+    };
+
+    let most_recent_year = most_recent.year;
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
+        y.parse()
+            .ok()
+            .filter(|y| (2015..=most_recent_year).contains(y))
+    }) {
+        Some(Some(y)) => y,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
+        None => most_recent_year,
+    };
+
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let (leaderboard, last_update) = context.aoc_client.get_private_leaderboard(year).await?;
+
+    let Some(a) = find_member(context, &leaderboard, user1, || {
+        find_pill(&event.content, user1)
+    }) else {
+        let error = html_error_message(html!("User '{user1}' not found", user1));
+        room.reply_to(event, error).await?;
+        return Ok(());
+    };
+    let Some(b) = find_member(context, &leaderboard, user2, || {
+        find_pill(&event.content, user2)
+    }) else {
+        let error = html_error_message(html!("User '{user2}' not found", user2));
+        room.reply_to(event, error).await?;
+        return Ok(());
+    };
+
+    let days = if year == most_recent.year {
+        most_recent.day
+    } else {
+        25
+    };
+
+    let view = view(&leaderboard, a, b, year, days);
+    let timezone = context.timezone(&event.sender).await;
+    let out = render(&view, format, timezone, last_update);
+
+    room.reply_to(event, html_message(out)).await?;
+
+    Ok(())
+}
+
+pub struct CompareView {
+    pub year: i32,
+    pub names: [String; 2],
+    pub days: Vec<CompareDay>,
+}
+
+pub struct CompareDay {
+    /// The solve times of part one and part two of both members, measured from
+    /// the unlock of the puzzle.
+    pub times: [[Option<TimeDelta>; 2]; 2],
+    /// Whether the first member completed the day before (`Less`), at the
+    /// same time as (`Equal`) or after (`Greater`) the second member, `None`
+    /// if neither has solved it.
+    pub faster: Option<Ordering>,
+    /// The difference of the local scores of both members up to this day.
+    pub score_diff: i64,
+}
+
+/// Compare the solve times and scores of both members on each of the first
+/// `days` days.
+pub fn view(
+    leaderboard: &PrivateLeaderboard,
+    a: &PrivateLeaderboardMember,
+    b: &PrivateLeaderboardMember,
+    year: i32,
+    days: u32,
+) -> CompareView {
+    let mut score_diff = 0i64;
+    let days = (1..=days)
+        .map(|d| {
+            let unlock = AocDay { year, day: d }.unlock_datetime();
+            let times =
+                [a, b].map(|m| [false, true].map(|part2| Some(m.star_ts(d, part2)? - unlock)));
+
+            let faster = match (completion_key(a, d), completion_key(b, d)) {
+                (None, None) => None,
+                (Some(_), None) => Some(Ordering::Less),
+                (None, Some(_)) => Some(Ordering::Greater),
+                (Some(key_a), Some(key_b)) => Some(key_a.cmp(&key_b)),
+            };
+
+            let score = |m| {
+                [false, true]
+                    .into_iter()
+                    .flat_map(|part2| leaderboard.star_score(m, d, part2))
+                    .sum::<u32>() as i64
+            };
+            score_diff += score(a) - score(b);
+
+            CompareDay {
+                times,
+                faster,
+                score_diff,
+            }
+        })
+        .collect();
+
+    CompareView {
+        year,
+        names: [a.display_name(), b.display_name()],
+        days,
+    }
+}
+
+fn render(
+    view: &CompareView,
+    format: TableFormat,
+    timezone: FixedOffset,
+    last_update: DateTime<Utc>,
+) -> Html {
+    let [name_a, name_b] = &view.names;
+
+    let mut table = Table::new([
+        "Day".to_owned(),
+        format!("Part 1 ({name_a})"),
+        format!("Part 1 ({name_b})"),
+        format!("Part 2 ({name_a})"),
+        format!("Part 2 ({name_b})"),
+        "Faster".to_owned(),
+        "Score Difference".to_owned(),
+    ]);
+
+    for (d, day) in (1..).zip(&view.days) {
+        let [a, b] = day.times;
+        let fmt_cell = |time: Option<TimeDelta>, other: Option<TimeDelta>| match time {
+            Some(time) => Cell::new(fmt_timedelta(time)).bold(other.is_none_or(|o| time < o)),
+            None => Cell::new(""),
+        };
+        let faster = match day.faster {
+            None => "",
+            Some(Ordering::Equal) => "tie",
+            Some(Ordering::Less) => name_a,
+            Some(Ordering::Greater) => name_b,
+        };
+
+        table.push([
+            Cell::new(d),
+            fmt_cell(a[0], b[0]),
+            fmt_cell(b[0], a[0]),
+            fmt_cell(a[1], b[1]),
+            fmt_cell(b[1], a[1]),
+            Cell::new(faster),
+            Cell::new(format!("{:+}", day.score_diff)),
+        ]);
+    }
+
+    let last_update = timezone
+        .from_utc_datetime(&last_update.naive_utc())
+        .format_ymd_hms_z();
+    html!(
+        r#"
+<h3>{name_a} vs. {name_b} (Advent of Code {year})</h3>
+{table}
+<sup>Last update: {last_update}</sup>
+"#,
+        name_a,
+        name_b,
+        year = view.year,
+        table = table.render(format),
+        last_update
+    )
+}
+
+/// Sort key for the completion of a day: members who have solved part two
+/// come first (ordered by time), then those who have only solved part one.
+fn completion_key(
+    member: &PrivateLeaderboardMember,
+    day: u32,
+) -> Option<(bool, Option<DateTime<Utc>>, DateTime<Utc>)> {
+    let p1 = member.star_ts(day, false)?;
+    let p2 = member.star_ts(day, true);
+    Some((p2.is_none(), p2, p1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::commands::aoc::testing::{config, last_update, leaderboard};
+
+    #[test]
+    fn snapshot() {
+        let config = config();
+        let leaderboard = leaderboard();
+        let member = |id| leaderboard.members.values().find(|m| m.id == id).unwrap();
+
+        // Bob has not solved part two of day 2, neither has solved day 3
+        let v = super::view(&leaderboard, member(1), member(2), 2024, 3);
+        let faster = v.days.iter().map(|d| d.faster).collect::<Vec<_>>();
+        assert_eq!(faster, [Some(Ordering::Less), Some(Ordering::Less), None]);
+        assert_eq!(v.days[1].times[1][1], None);
+        assert_eq!(v.days[2].times, [[None; 2]; 2]);
+
+        insta::assert_snapshot!(
+            "alice_bob",
+            render(&v, TableFormat::Table, config.local_timezone, last_update()).as_str()
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use matrix_sdk::{
+    ruma::events::{
+        reaction::ReactionEventContent, relation::Annotation,
+        room::message::OriginalRoomMessageEvent,
+    },
+    Room,
+};
+
+use crate::{
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{message, RoomExt},
+    },
+};
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let Some(&aoc) = context
+        .users
+        .by_matrix
+        .get(&event.sender)
+        .and_then(|u| u.aoc.first())
+    else {
+        return send_error(
+            &room,
+            event,
+            "Your matrix account is not linked to an AoC account",
+        )
+        .await;
+    };
+
+    let competitive = match cmd.get_from_kwargs_or_args("state") {
+        Some("on") => true,
+        Some("off") => false,
+        Some(_) => return send_error(&room, event, "Failed to parse argument 'state'").await,
+        None => {
+            let content = if context.competitive().await(aoc) {
+                "You are currently included in rankings."
+            } else {
+                "You are currently excluded from rankings."
+            };
+            room.reply_to(event, message(content)).await?;
+            return Ok(());
+        }
+    };
+
+    context.competitive.set(aoc, competitive).await?;
+
+    room.send(ReactionEventContent::new(Annotation::new(
+        event.event_id.clone(),
+        "✅️".into(),
+    )))
+    .await?;
+
+    Ok(())
+}
@@ -1,14 +1,18 @@
-use std::fmt::Write;
-
-use chrono::TimeZone;
+use chrono::{DateTime, FixedOffset, TimeDelta, TimeZone, Utc};
 use matrix_sdk::{
     ruma::{api::client::error::ErrorKind, events::room::message::OriginalRoomMessageEvent},
     Room,
 };
 
+use super::{leaderboard::reply_fetch_error, member::MemberView};
 use crate::{
-    aoc::{client::Parts, day::AocDay, models::PrivateLeaderboardMember},
-    context::Context,
+    aoc::{
+        client::{daily_leaderboard, Parts},
+        day::AocDay,
+        models::{AocId, PrivateLeaderboard, PrivateLeaderboardMember},
+    },
+    config::Config,
+    context::{Context, ContextUsers},
     matrix::{
         commands::{parser::ParsedCommand, send_error},
         utils::{error_message, html_message, RoomExt},
@@ -16,6 +20,8 @@ use crate::{
     utils::{
         datetime::DateTimeExt,
         fmt::{fmt_rank, fmt_timedelta},
+        html::{html, Html},
+        table::{Cell, Table, TableFormat},
     },
 };
 
@@ -71,57 +77,133 @@ pub async fn invoke(
         None => 0,
     };
 
-    let (leaderboard, last_update) = match context
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let result = context
         .aoc_client
         .get_daily_private_leaderboard(year, day, parts)
-        .await
-    {
-        Ok(resp) => resp,
-        Err(err) => match err.downcast::<reqwest::Error>() {
-            Ok(err) => {
-                if let Some(status) = err.status() {
-                    room.reply_to(
-                        event,
-                        error_message(format!(
-                            "Failed to fetch private leaderboard for {year} ({status})"
-                        )),
-                    )
-                    .await?;
-                    return Ok(());
-                } else {
-                    return Err(err.into());
-                }
-            }
-            Err(err) => return Err(err),
-        },
+        .await;
+    let Some((leaderboard, last_update)) = reply_fetch_error(&room, event, year, result).await?
+    else {
+        return Ok(());
     };
-    let last_update = context
-        .config
-        .local_timezone
-        .from_utc_datetime(&last_update.naive_utc())
-        .format_ymd_hms_z();
+    let competitive = context.competitive().await;
+    let mut view = view(
+        &leaderboard,
+        &context.users,
+        competitive,
+        AocDay { year, day },
+        parts,
+        rows,
+        offset,
+    );
+    for row in view.rows.iter_mut().chain(&mut view.non_competitive) {
+        row.member.resolve_matrix_name(&room).await;
+    }
+    let timezone = context.timezone(&event.sender).await;
+    let leaderboard = render(&view, &context.config, format, timezone, last_update);
+
+    if let Err(err) = room.reply_to(event, html_message(leaderboard)).await {
+        if err
+            .as_client_api_error()
+            .and_then(|err| err.error_kind())
+            .is_some_and(|kind| matches!(kind, ErrorKind::TooLarge))
+        {
+            room.reply_to(
+                event,
+                error_message(
+                    "The requested leaderboard slice would be too large to fit in a matrix \
+                     message. Try to reduce the number of rows.",
+                ),
+            )
+            .await?;
+            return Ok(());
+        } else {
+            return Err(err.into());
+        }
+    }
+
+    Ok(())
+}
+
+pub struct DayView {
+    pub day: AocDay,
+    pub parts: Parts,
+    pub rows: Vec<DayRow>,
+    /// Members excluded from the ranking, only populated if the requested
+    /// slice includes the end of the leaderboard.
+    pub non_competitive: Vec<DayRow>,
+}
+
+pub struct DayRow {
+    /// The rank of the member, `None` for non-competitive members.
+    pub rank: Option<usize>,
+    pub local_score: u32,
+    pub stars: u32,
+    pub completion: DateTime<Utc>,
+    /// The time from the puzzle unlock (or from part one for `Parts::P2`) to
+    /// the completion.
+    pub delta: TimeDelta,
+    pub member: MemberView,
+}
 
-    let mut members = leaderboard.members.into_values().collect::<Vec<_>>();
+/// Rank the competitive members of the given daily leaderboard that have
+/// solved the puzzle and select the requested slice.
+pub fn view(
+    leaderboard: &PrivateLeaderboard,
+    users: &ContextUsers,
+    competitive: impl Fn(AocId) -> bool,
+    day: AocDay,
+    parts: Parts,
+    rows: usize,
+    offset: usize,
+) -> DayView {
+    let ranked = daily_leaderboard(leaderboard.filtered(&competitive), day.day, parts);
+    let mut members = ranked.members.values().collect::<Vec<_>>();
+    let mut non_competitive = leaderboard
+        .members
+        .values()
+        .filter(|m| !competitive(m.id) && m.stars > 0)
+        .collect::<Vec<_>>();
     members.sort_unstable();
+    non_competitive.sort_unstable();
+    if offset + rows < members.iter().filter(|m| m.stars > 0).count() {
+        non_competitive.clear();
+    }
 
-    let parts_title = match parts {
-        Parts::P1 => "/1",
-        Parts::P2 => "/2",
-        Parts::Both => "",
+    let unlock = day.unlock_datetime();
+    let row = |rank, member: &PrivateLeaderboardMember| {
+        let start = match parts {
+            Parts::P1 | Parts::Both => unlock,
+            Parts::P2 => {
+                member
+                    .completion_day_level
+                    .get(&day.day)
+                    .unwrap()
+                    .fst
+                    .get_star_ts
+            }
+        };
+        DayRow {
+            rank,
+            local_score: member.local_score,
+            stars: member.stars,
+            completion: member.last_star_ts,
+            delta: member.last_star_ts - start,
+            member: MemberView::new(users, member.id, member.display_name(), Some(day.year)),
+        }
     };
-    let mut leaderboard = format!(
-        r#"
-<h3>Private Leaderboard (Advent of Code {year}/{day:02}{parts_title})</h3>
-<table>
-<tr> <th>Rank</th> <th>Local Score</th> <th>Stars</th> <th>Completion</th> <th>AoC Name</th> <th>Matrix User</th> <th>Repository</th> </tr>
-"#
-    );
-
-    let unlock = AocDay { year, day }.unlock_datetime();
 
     let mut last_score = u32::MAX;
     let mut rank = 0;
-    for (rank, member) in members
+    let rows = members
         .into_iter()
         .enumerate()
         .map(|(i, member)| {
@@ -134,108 +216,149 @@ pub async fn invoke(
         .filter(|(_, m)| m.stars > 0)
         .skip(offset)
         .take(rows)
-    {
-        let PrivateLeaderboardMember {
-            local_score, stars, ..
-        } = member;
-
-        let name = member.display_name();
-
-        let matrix_name = context
-            .users
-            .by_aoc
-            .get(&member.id)
-            .and_then(|u| u.matrix.as_ref())
-            .map(|m| m.matrix_to_uri().to_string())
-            .unwrap_or_default();
-
-        let repo = context
-            .users
-            .by_aoc
-            .get(&member.id)
-            .and_then(|u| u.repo.as_deref())
-            .unwrap_or_default();
-        let repo_title = context
-            .config
-            .aoc
-            .repo_rules
-            .match_and_replace(repo)
-            .map(|m| m.replacement);
-        let repo_title = repo_title.as_deref().unwrap_or(repo);
-
-        let (m, m_) = if rank <= 3 {
-            ("<b>", "</b>")
-        } else {
-            Default::default()
-        };
+        .map(|(rank, member)| row(Some(rank), member))
+        .collect();
+
+    DayView {
+        day,
+        parts,
+        rows,
+        non_competitive: non_competitive.into_iter().map(|m| row(None, m)).collect(),
+    }
+}
 
-        let rank = fmt_rank(rank);
+fn render(
+    view: &DayView,
+    config: &Config,
+    format: TableFormat,
+    timezone: FixedOffset,
+    last_update: DateTime<Utc>,
+) -> Html {
+    let DayView {
+        day: AocDay { year, day },
+        parts,
+        rows,
+        non_competitive,
+    } = view;
 
-        let completion = context
-            .config
-            .local_timezone
-            .from_utc_datetime(&member.last_star_ts.naive_utc())
+    let parts_title = match parts {
+        Parts::P1 => "/1",
+        Parts::P2 => "/2",
+        Parts::Both => "",
+    };
+    let fmt_completion = |row: &DayRow, bold: bool| {
+        let completion = timezone
+            .from_utc_datetime(&row.completion.naive_utc())
             .format_ymd_hms();
+        let delta = fmt_timedelta(row.delta);
 
-        let start = match parts {
-            Parts::P1 | Parts::Both => unlock,
-            Parts::P2 => {
-                member
-                    .completion_day_level
-                    .get(&day)
-                    .unwrap()
-                    .fst
-                    .get_star_ts
-            }
+        let html = if bold {
+            html!("{completion}(<b>{delta}</b>)", completion, delta)
+        } else {
+            html!("{completion}({delta})", completion, delta)
         };
-        let delta = fmt_timedelta(member.last_star_ts - start);
+        Cell::html(html, format!("{completion} ({delta})"))
+    };
+
+    let mut table = Table::new([
+        "Rank",
+        "Local Score",
+        "Stars",
+        "Completion",
+        "AoC Name",
+        "Matrix User",
+        "Repository",
+    ]);
+    for row in rows {
+        let rank = row.rank.unwrap_or_default();
+        let bold = rank <= 3;
+        table.push(
+            [
+                Cell::new(fmt_rank(rank)).bold(bold),
+                Cell::new(row.local_score).bold(bold),
+                Cell::new(row.stars).bold(bold),
+                fmt_completion(row, bold),
+            ]
+            .into_iter()
+            .chain(row.member.cells(config, bold)),
+        );
+    }
+
+    let mut leaderboard = html!(
+        r#"
+<h3>Private Leaderboard (Advent of Code {year}/{day}{parts_title})</h3>
+{table}
+"#,
+        year,
+        day = format!("{day:02}"),
+        parts_title,
+        table = table.render(format)
+    );
 
-        let link_prefix = &context.config.matrix.link_prefix;
-        write!(
-            &mut leaderboard,
+    if !non_competitive.is_empty() {
+        let mut table = Table::new([
+            "Stars",
+            "Completion",
+            "AoC Name",
+            "Matrix User",
+            "Repository",
+        ]);
+        for row in non_competitive {
+            table.push(
+                [Cell::new(row.stars), fmt_completion(row, false)]
+                    .into_iter()
+                    .chain(row.member.cells(config, false)),
+            );
+        }
+
+        leaderboard.push(html!(
             r#"
-<tr>
-    <td>{m}{rank}{m_}</td>
-    <td>{m}{local_score}{m_}</td>
-    <td>{m}{stars}{m_}</td>
-    <td>{completion}({m}{delta}{m_})</td>
-    <td>{m}{name}{m_}</td>
-    <td>{matrix_name}</td>
-    <td>{m}<a href="{link_prefix}{repo}">{repo_title}</a>{m_}</td>
-</tr>
-"#
-        )
-        .unwrap();
+<h4>Non-Competitive</h4>
+{table}
+"#,
+            table = table.render(format)
+        ));
     }
 
-    write!(
-        &mut leaderboard,
+    let last_update = timezone
+        .from_utc_datetime(&last_update.naive_utc())
+        .format_ymd_hms_z();
+    leaderboard.push(html!(
         r#"
-</table>
 <sup>Last update: {last_update}</sup>
-"#
-    )
-    .unwrap();
+"#,
+        last_update
+    ));
 
-    if let Err(err) = room.reply_to(event, html_message(leaderboard)).await {
-        if err
-            .as_client_api_error()
-            .and_then(|err| err.error_kind())
-            .is_some_and(|kind| matches!(kind, ErrorKind::TooLarge))
-        {
-            room.reply_to(
-                event,
-                error_message(
-                    "The requested leaderboard slice would be too large to fit in a matrix \
-                     message. Try to reduce the number of rows.",
-                ),
-            )
-            .await?;
-            return Ok(());
-        } else {
-            return Err(err.into());
+    leaderboard
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::commands::aoc::testing::{config, last_update, leaderboard};
+
+    #[test]
+    fn snapshot() {
+        let config = config();
+        let users = ContextUsers::from_config(&config);
+        let competitive = |id| users.by_aoc.get(&id).is_none_or(|u| u.competitive);
+        let day = AocDay { year: 2024, day: 2 };
+
+        for parts in [Parts::Both, Parts::P2] {
+            let leaderboard = daily_leaderboard(leaderboard(), day.day, parts);
+            let v = super::view(&leaderboard, &users, competitive, day, parts, 20, 0);
+            insta::assert_snapshot!(
+                format!("{parts:?}").to_lowercase(),
+                render(
+                    &v,
+                    &config,
+                    TableFormat::Table,
+                    config.local_timezone,
+                    last_update()
+                )
+                .as_str()
+            );
         }
     }
-
-    Ok(())
 }
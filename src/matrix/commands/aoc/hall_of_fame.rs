@@ -0,0 +1,173 @@
+use std::cmp::Reverse;
+
+use matrix_sdk::{ruma::events::room::message::OriginalRoomMessageEvent, Room};
+
+use crate::{
+    aoc::{
+        day::AocDay,
+        models::{AocId, PrivateLeaderboard},
+        stats::{all_time_totals, competition_ranks, winner},
+    },
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{html_message, RoomExt},
+    },
+    utils::{
+        fmt::fmt_rank,
+        html::html,
+        table::{Cell, Table},
+    },
+};
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let rows = match cmd
+        .get_from_kwargs_or_args("rows")
+        .map(|y| y.parse().ok().filter(|x| (0..=200).contains(x)))
+    {
+        Some(Some(x)) => x,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'rows'").await,
+        None => context.config.aoc.leaderboard_rows,
+    };
+
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let most_recent_year = AocDay::most_recent().year;
+    let leaderboards = context
+        .aoc_client
+        .get_private_leaderboards(2015..=most_recent_year)
+        .await?;
+    let competitive = context.competitive().await;
+    let filtered = |include: &dyn Fn(AocId) -> bool| {
+        leaderboards
+            .iter()
+            .map(|(year, leaderboard)| (*year, leaderboard.filtered(include)))
+            .collect::<Vec<_>>()
+    };
+    let ranked = filtered(&competitive);
+
+    let mut winners = Table::new([
+        "Year",
+        "Winner",
+        "Local Score",
+        "Members",
+        "Full Completions",
+    ]);
+    for ((year, leaderboard), (_, ranked)) in leaderboards.iter().zip(&ranked).rev() {
+        let (winner, local_score) = match winner(ranked) {
+            // the leader of a running event is not the winner yet
+            Some(m) if !AocDay::is_over(*year) => (
+                Cell::html(
+                    html!("{name} <i>(in progress)</i>", name = m.display_name()),
+                    format!("{} (in progress)", m.display_name()),
+                ),
+                m.local_score.to_string(),
+            ),
+            Some(m) => (
+                Cell::new(m.display_name()).bold(true),
+                m.local_score.to_string(),
+            ),
+            None => (Cell::new(""), String::new()),
+        };
+        let members = leaderboard.members.values().filter(|m| m.stars > 0).count();
+        let full_completions = leaderboard
+            .members
+            .values()
+            .filter(|m| m.stars == 2 * AocDay::DAYS)
+            .count();
+
+        winners.push([
+            Cell::new(year),
+            winner,
+            Cell::new(local_score),
+            Cell::new(members),
+            Cell::new(full_completions),
+        ]);
+    }
+
+    let totals = |leaderboards: &[(i32, PrivateLeaderboard)]| {
+        all_time_totals(leaderboards.iter().map(|(y, lb)| (*y, lb)), |id| {
+            context.users.canonical_aoc_id(id)
+        })
+    };
+    let mut non_competitive = totals(&filtered(&|id| !competitive(id)));
+    non_competitive
+        .sort_unstable_by_key(|t| (Reverse(t.full_completions.len()), Reverse(t.stars), t.id));
+    let mut totals = totals(&ranked);
+    totals.sort_unstable_by_key(|t| {
+        (
+            Reverse(t.wins.len()),
+            Reverse(t.full_completions.len()),
+            Reverse(t.stars),
+            t.id,
+        )
+    });
+
+    let mut table = Table::new([
+        "Rank",
+        "AoC Name",
+        "Wins",
+        "Full Completions",
+        "Stars",
+        "Years",
+    ]);
+    let ranks = competition_ranks(totals, |t| {
+        (t.wins.len(), t.full_completions.len(), t.stars)
+    });
+    for (rank, totals) in ranks.take(rows) {
+        table.push([
+            Cell::new(fmt_rank(rank)),
+            Cell::new(totals.name),
+            Cell::new(totals.wins.len()),
+            Cell::new(totals.full_completions.len()),
+            Cell::new(totals.stars),
+            Cell::new(totals.years.len()),
+        ]);
+    }
+
+    let mut out = html!(
+        r#"
+<h3>Hall of Fame</h3>
+{winners}
+<h3>All-Time Totals</h3>
+{table}
+"#,
+        winners = winners.render(format),
+        table = table.render(format)
+    );
+
+    if !non_competitive.is_empty() {
+        let mut table = Table::new(["AoC Name", "Full Completions", "Stars", "Years"]);
+        for totals in non_competitive.into_iter().take(rows) {
+            table.push([
+                Cell::new(totals.name),
+                Cell::new(totals.full_completions.len()),
+                Cell::new(totals.stars),
+                Cell::new(totals.years.len()),
+            ]);
+        }
+        out.push(html!(
+            r#"
+<h4>Non-Competitive</h4>
+{table}
+"#,
+            table = table.render(format)
+        ));
+    }
+
+    room.reply_to(event, html_message(out)).await?;
+
+    Ok(())
+}
@@ -1,19 +1,30 @@
-use std::fmt::Write;
+use std::cmp::Reverse;
 
-use chrono::TimeZone;
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
 use matrix_sdk::{
     ruma::{api::client::error::ErrorKind, events::room::message::OriginalRoomMessageEvent},
     Room,
 };
 
+use super::member::MemberView;
 use crate::{
-    aoc::{day::AocDay, models::PrivateLeaderboardMember},
-    context::Context,
+    aoc::{
+        day::AocDay,
+        models::{AocId, PrivateLeaderboard, PrivateLeaderboardMember},
+        stats::{all_time_totals, competition_ranks, MemberTotals},
+    },
+    config::Config,
+    context::{Context, ContextUsers},
     matrix::{
         commands::{parser::ParsedCommand, send_error},
         utils::{error_message, html_message, RoomExt},
     },
-    utils::{datetime::DateTimeExt, fmt::fmt_rank},
+    utils::{
+        datetime::DateTimeExt,
+        fmt::fmt_rank,
+        html::{html, Html},
+        table::{Cell, Table, TableFormat},
+    },
 };
 
 pub async fn invoke(
@@ -23,14 +34,17 @@ pub async fn invoke(
     mut cmd: ParsedCommand<'_>,
 ) -> anyhow::Result<()> {
     let most_recent_year = AocDay::most_recent().year;
-    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
-        y.parse()
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| match y {
+        "all" => Some(None),
+        _ => y
+            .parse()
             .ok()
             .filter(|y| (2015..=most_recent_year).contains(y))
+            .map(Some),
     }) {
         Some(Some(y)) => y,
         Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
-        None => most_recent_year,
+        None => Some(most_recent_year),
     };
 
     let rows = match cmd
@@ -51,11 +65,55 @@ pub async fn invoke(
         None => 0,
     };
 
-    let (leaderboard, last_update) = match context.aoc_client.get_private_leaderboard(year).await {
-        Ok(resp) => resp,
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let Some(year) = year else {
+        return invoke_all_time(event, room, context, rows, offset, format).await;
+    };
+
+    let result = context.aoc_client.get_private_leaderboard(year).await;
+    let Some((leaderboard, last_update)) = reply_fetch_error(&room, event, year, result).await?
+    else {
+        return Ok(());
+    };
+    let competitive = context.competitive().await;
+    let mut view = view(
+        &leaderboard,
+        &context.users,
+        competitive,
+        year,
+        rows,
+        offset,
+    );
+    for row in view.rows.iter_mut().chain(&mut view.non_competitive) {
+        row.member.resolve_matrix_name(&room).await;
+    }
+    let timezone = context.timezone(&event.sender).await;
+    let leaderboard = render(&view, &context.config, format, timezone, last_update);
+
+    send_leaderboard(event, room, leaderboard).await
+}
+
+/// Reply with an error message if fetching the leaderboard of the given year
+/// failed with an http error status, in which case `None` is returned.
+pub async fn reply_fetch_error<T>(
+    room: &Room,
+    event: &OriginalRoomMessageEvent,
+    year: i32,
+    result: anyhow::Result<T>,
+) -> anyhow::Result<Option<T>> {
+    match result {
+        Ok(resp) => Ok(Some(resp)),
         Err(err) => match err.downcast::<reqwest::Error>() {
-            Ok(err) => {
-                if let Some(status) = err.status() {
+            Ok(err) => match err.status() {
+                Some(status) => {
                     room.reply_to(
                         event,
                         error_message(format!(
@@ -63,112 +121,309 @@ pub async fn invoke(
                         )),
                     )
                     .await?;
-                    return Ok(());
-                } else {
-                    return Err(err.into());
+                    Ok(None)
                 }
-            }
-            Err(err) => return Err(err),
+                None => Err(err.into()),
+            },
+            Err(err) => Err(err),
         },
-    };
-    let last_update = context
-        .config
-        .local_timezone
-        .from_utc_datetime(&last_update.naive_utc())
-        .format_ymd_hms_z();
+    }
+}
+
+async fn invoke_all_time(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    rows: usize,
+    offset: usize,
+    format: TableFormat,
+) -> anyhow::Result<()> {
+    let leaderboards = context
+        .aoc_client
+        .get_private_leaderboards(2015..=AocDay::most_recent().year)
+        .await?;
+
+    let competitive = context.competitive().await;
+    let mut view = view_all_time(&leaderboards, &context.users, competitive, rows, offset);
+    for row in view.rows.iter_mut().chain(&mut view.non_competitive) {
+        row.member.resolve_matrix_name(&room).await;
+    }
+    let leaderboard = render_all_time(&view, &context.config, format);
 
-    let mut members = leaderboard.members.into_values().collect::<Vec<_>>();
+    send_leaderboard(event, room, leaderboard).await
+}
+
+pub struct LeaderboardView {
+    pub year: i32,
+    pub rows: Vec<LeaderboardRow>,
+    /// Members excluded from the ranking, only populated if the requested
+    /// slice includes the end of the leaderboard.
+    pub non_competitive: Vec<LeaderboardRow>,
+}
+
+pub struct LeaderboardRow {
+    /// The rank of the member, `None` for non-competitive members.
+    pub rank: Option<usize>,
+    pub local_score: u32,
+    pub global_score: u32,
+    pub stars: u32,
+    pub member: MemberView,
+}
+
+pub struct AllTimeView {
+    pub rows: Vec<AllTimeRow>,
+    /// Members excluded from the ranking, only populated if the requested
+    /// slice includes the end of the leaderboard.
+    pub non_competitive: Vec<AllTimeRow>,
+}
+
+pub struct AllTimeRow {
+    /// The rank of the member, `None` for non-competitive members.
+    pub rank: Option<usize>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub years: usize,
+    pub member: MemberView,
+}
+
+/// Rank the competitive members of the given leaderboard and select the
+/// requested slice.
+pub fn view(
+    leaderboard: &PrivateLeaderboard,
+    users: &ContextUsers,
+    competitive: impl Fn(AocId) -> bool,
+    year: i32,
+    rows: usize,
+    offset: usize,
+) -> LeaderboardView {
+    let ranked = leaderboard.filtered(&competitive);
+    let mut members = ranked.members.values().collect::<Vec<_>>();
+    let mut non_competitive = leaderboard
+        .members
+        .values()
+        .filter(|m| !competitive(m.id) && m.stars > 0)
+        .collect::<Vec<_>>();
     members.sort_unstable();
+    non_competitive.sort_unstable();
+    if offset + rows < members.len() {
+        non_competitive.clear();
+    }
+
+    let row = |rank, member: &PrivateLeaderboardMember| LeaderboardRow {
+        rank,
+        local_score: member.local_score,
+        global_score: member.global_score,
+        stars: member.stars,
+        member: MemberView::new(users, member.id, member.display_name(), Some(year)),
+    };
 
-    let mut leaderboard = format!(
+    let rows = competition_ranks(members, |m| m.local_score)
+        .skip(offset)
+        .take(rows)
+        .map(|(rank, member)| row(Some(rank), member))
+        .collect();
+
+    LeaderboardView {
+        year,
+        rows,
+        non_competitive: non_competitive.into_iter().map(|m| row(None, m)).collect(),
+    }
+}
+
+fn render(
+    view: &LeaderboardView,
+    config: &Config,
+    format: TableFormat,
+    timezone: FixedOffset,
+    last_update: DateTime<Utc>,
+) -> Html {
+    let LeaderboardView {
+        year,
+        rows,
+        non_competitive,
+    } = view;
+
+    let mut table = Table::new([
+        "Rank",
+        "Local Score",
+        "Global Score",
+        "Stars",
+        "AoC Name",
+        "Matrix User",
+        "Repository",
+    ]);
+    for row in rows {
+        let rank = row.rank.unwrap_or_default();
+        let bold = rank <= 3;
+        table.push(
+            [
+                Cell::new(fmt_rank(rank)).bold(bold),
+                Cell::new(row.local_score).bold(bold),
+                Cell::new(row.global_score).bold(bold),
+                Cell::new(row.stars).bold(bold),
+            ]
+            .into_iter()
+            .chain(row.member.cells(config, bold)),
+        );
+    }
+
+    let mut leaderboard = html!(
         r#"
 <h3>Private Leaderboard (Advent of Code {year})</h3>
-<table>
-<tr> <th>Rank</th> <th>Local Score</th> <th>Global Score</th> <th>Stars</th> <th>AoC Name</th> <th>Matrix User</th> <th>Repository</th> </tr>
-"#
+{table}
+"#,
+        year,
+        table = table.render(format)
     );
 
-    let mut last_score = u32::MAX;
-    let mut rank = 0;
-    for (rank, member) in members
-        .into_iter()
-        .enumerate()
-        .map(|(i, member)| {
-            if member.local_score != last_score {
-                last_score = member.local_score;
-                rank = i + 1;
-            }
-            (rank, member)
-        })
-        .skip(offset)
-        .take(rows)
-    {
-        let PrivateLeaderboardMember {
-            local_score,
-            global_score,
+    if !non_competitive.is_empty() {
+        let mut table = Table::new(["Stars", "AoC Name", "Matrix User", "Repository"]);
+        for row in non_competitive {
+            table.push(
+                [Cell::new(row.stars)]
+                    .into_iter()
+                    .chain(row.member.cells(config, false)),
+            );
+        }
+
+        leaderboard.push(html!(
+            r#"
+<h4>Non-Competitive</h4>
+{table}
+"#,
+            table = table.render(format)
+        ));
+    }
+
+    let last_update = timezone
+        .from_utc_datetime(&last_update.naive_utc())
+        .format_ymd_hms_z();
+    leaderboard.push(html!(
+        r#"
+<sup>Last update: {last_update}</sup>
+"#,
+        last_update
+    ));
+
+    leaderboard
+}
+
+/// Rank the competitive members by their total stars and local score over all
+/// years and select the requested slice.
+pub fn view_all_time(
+    leaderboards: &[(i32, PrivateLeaderboard)],
+    users: &ContextUsers,
+    competitive: impl Fn(AocId) -> bool,
+    rows: usize,
+    offset: usize,
+) -> AllTimeView {
+    let totals = |include: &dyn Fn(AocId) -> bool| {
+        let filtered = leaderboards
+            .iter()
+            .map(|(y, lb)| (*y, lb.filtered(include)))
+            .collect::<Vec<_>>();
+        let mut totals = all_time_totals(filtered.iter().map(|(y, lb)| (*y, lb)), |id| {
+            users.canonical_aoc_id(id)
+        });
+        totals.sort_unstable_by_key(|t| (Reverse(t.stars), Reverse(t.local_score), t.id));
+        totals
+    };
+    let ranked = totals(&competitive);
+    let mut non_competitive = totals(&|id| !competitive(id));
+    if offset + rows < ranked.len() {
+        non_competitive.clear();
+    }
+
+    let row = |rank, totals: MemberTotals| {
+        let MemberTotals {
+            id,
+            name,
+            years,
             stars,
+            local_score,
             ..
-        } = member;
-
-        let name = member.display_name();
-
-        let matrix_name = context
-            .users
-            .by_aoc
-            .get(&member.id)
-            .and_then(|u| u.matrix.as_ref())
-            .map(|m| m.matrix_to_uri().to_string())
-            .unwrap_or_default();
-
-        let repo = context
-            .users
-            .by_aoc
-            .get(&member.id)
-            .and_then(|u| u.repo.as_deref())
-            .unwrap_or_default();
-        let repo_title = context
-            .config
-            .aoc
-            .repo_rules
-            .match_and_replace(repo)
-            .map(|m| m.replacement);
-        let repo_title = repo_title.as_deref().unwrap_or(repo);
-
-        let (m, m_) = if rank <= 3 {
-            ("<b>", "</b>")
-        } else {
-            Default::default()
-        };
+        } = totals;
+        AllTimeRow {
+            rank,
+            stars,
+            local_score,
+            years: years.len(),
+            member: MemberView::new(users, id, name, None),
+        }
+    };
 
-        let rank = fmt_rank(rank);
+    let rows = competition_ranks(ranked, |t| (t.stars, t.local_score))
+        .skip(offset)
+        .take(rows)
+        .map(|(rank, totals)| row(Some(rank), totals))
+        .collect();
 
-        let link_prefix = &context.config.matrix.link_prefix;
-        write!(
-            &mut leaderboard,
-            r#"
-<tr>
-    <td>{m}{rank}{m_}</td>
-    <td>{m}{local_score}{m_}</td>
-    <td>{m}{global_score}{m_}</td>
-    <td>{m}{stars}{m_}</td>
-    <td>{m}{name}{m_}</td>
-    <td>{matrix_name}</td>
-    <td>{m}<a href="{link_prefix}{repo}">{repo_title}</a>{m_}</td>
-</tr>
-"#
-        )
-        .unwrap();
+    AllTimeView {
+        rows,
+        non_competitive: non_competitive.into_iter().map(|t| row(None, t)).collect(),
+    }
+}
+
+fn render_all_time(view: &AllTimeView, config: &Config, format: TableFormat) -> Html {
+    let mut table = Table::new([
+        "Rank",
+        "Stars",
+        "Local Score",
+        "Years",
+        "AoC Name",
+        "Matrix User",
+        "Repository",
+    ]);
+    for row in &view.rows {
+        let rank = row.rank.unwrap_or_default();
+        let bold = rank <= 3;
+        table.push(
+            [
+                Cell::new(fmt_rank(rank)).bold(bold),
+                Cell::new(row.stars).bold(bold),
+                Cell::new(row.local_score).bold(bold),
+                Cell::new(row.years).bold(bold),
+            ]
+            .into_iter()
+            .chain(row.member.cells(config, bold)),
+        );
     }
 
-    write!(
-        &mut leaderboard,
+    let mut leaderboard = html!(
         r#"
-</table>
-<sup>Last update: {last_update}</sup>
-"#
-    )
-    .unwrap();
+<h3>Private Leaderboard (Advent of Code, all years)</h3>
+{table}
+"#,
+        table = table.render(format)
+    );
+
+    if !view.non_competitive.is_empty() {
+        let mut table = Table::new(["Stars", "Years", "AoC Name", "Matrix User", "Repository"]);
+        for row in &view.non_competitive {
+            table.push(
+                [Cell::new(row.stars), Cell::new(row.years)]
+                    .into_iter()
+                    .chain(row.member.cells(config, false)),
+            );
+        }
 
+        leaderboard.push(html!(
+            r#"
+<h4>Non-Competitive</h4>
+{table}
+"#,
+            table = table.render(format)
+        ));
+    }
+
+    leaderboard
+}
+
+async fn send_leaderboard(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    leaderboard: Html,
+) -> anyhow::Result<()> {
     if let Err(err) = room.reply_to(event, html_message(leaderboard)).await {
         if err
             .as_client_api_error()
@@ -191,3 +446,43 @@ pub async fn invoke(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::commands::aoc::testing::{config, last_update, leaderboard};
+
+    #[test]
+    fn snapshot() {
+        let config = config();
+        let users = ContextUsers::from_config(&config);
+        let competitive = |id| users.by_aoc.get(&id).is_none_or(|u| u.competitive);
+
+        let v = super::view(&leaderboard(), &users, competitive, 2024, 20, 0);
+        for format in [TableFormat::Table, TableFormat::Compact, TableFormat::Text] {
+            insta::assert_snapshot!(
+                format!("{format:?}").to_lowercase(),
+                render(&v, &config, format, config.local_timezone, last_update()).as_str()
+            );
+        }
+
+        let v = view_all_time(&[(2024, leaderboard())], &users, competitive, 2, 1);
+        insta::assert_snapshot!(
+            "all_time",
+            render_all_time(&v, &config, TableFormat::Table).as_str()
+        );
+    }
+
+    #[test]
+    fn slice() {
+        let config = config();
+        let users = ContextUsers::from_config(&config);
+        let competitive = |id| users.by_aoc.get(&id).is_none_or(|u| u.competitive);
+
+        let v = super::view(&leaderboard(), &users, competitive, 2024, 2, 1);
+        let names = v.rows.iter().map(|r| &*r.member.name).collect::<Vec<_>>();
+        assert_eq!(names, ["Bob <b>", "[anonymous user #3]"]);
+        assert_eq!(v.rows[0].rank, Some(2));
+        assert!(v.non_competitive.is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+use matrix_sdk::{ruma::OwnedUserId, Room};
+
+use crate::{
+    aoc::models::AocId,
+    config::Config,
+    context::ContextUsers,
+    matrix::utils::{room_display_name, user_pill},
+    utils::{
+        html::{link, Html},
+        table::Cell,
+    },
+};
+
+/// The name, matrix user and repository of a leaderboard member.
+pub struct MemberView {
+    pub name: String,
+    pub matrix: Option<MatrixUser>,
+    pub repo: Option<String>,
+}
+
+/// A matrix user and the name to title their pill with.
+pub struct MatrixUser {
+    pub id: OwnedUserId,
+    /// The display name in the room, or the user id until resolved with
+    /// [`MemberView::resolve_matrix_name`].
+    pub name: String,
+}
+
+impl MatrixUser {
+    pub fn new(id: OwnedUserId) -> Self {
+        let name = id.to_string();
+        Self { id, name }
+    }
+
+    pub fn pill(&self) -> Html {
+        user_pill(&self.id, &self.name)
+    }
+}
+
+impl MemberView {
+    /// Look up the matrix user and repository of the given AoC account. The
+    /// repository of the given year is used, or the latest one if `year` is
+    /// `None`.
+    pub fn new(users: &ContextUsers, id: AocId, name: String, year: Option<i32>) -> Self {
+        let user = users.by_aoc.get(&id);
+        Self {
+            name,
+            matrix: user.and_then(|u| u.matrix.clone()).map(MatrixUser::new),
+            repo: user
+                .and_then(|u| match year {
+                    Some(year) => u.repo.get(year),
+                    None => u.repo.latest(),
+                })
+                .map(Into::into),
+        }
+    }
+
+    /// Title the pill of the matrix user with their display name in the room.
+    pub async fn resolve_matrix_name(&mut self, room: &Room) {
+        if let Some(matrix) = &mut self.matrix {
+            matrix.name = room_display_name(room, &matrix.id).await;
+        }
+    }
+
+    /// The cells for the name, matrix user and repository columns.
+    pub fn cells(&self, config: &Config, bold: bool) -> [Cell; 3] {
+        [
+            Cell::new(&self.name).bold(bold),
+            matrix_user_cell(self.matrix.as_ref()),
+            repo_cell(config, self.repo.as_deref()).bold(bold),
+        ]
+    }
+}
+
+/// A cell linking to the given matrix user.
+pub fn matrix_user_cell(user: Option<&MatrixUser>) -> Cell {
+    match user {
+        Some(user) => Cell::html(user.pill(), &user.name),
+        None => Cell::new(""),
+    }
+}
+
+/// A cell linking to the given repository, titled according to the repo rules.
+pub fn repo_cell(config: &Config, repo: Option<&str>) -> Cell {
+    let Some(repo) = repo else {
+        return Cell::new("");
+    };
+    let repo_title = config
+        .aoc
+        .repo_rules
+        .match_and_replace(repo)
+        .map(|m| m.replacement);
+    let repo_title = repo_title.as_deref().unwrap_or(repo);
+    let link_prefix = &config.matrix.link_prefix;
+    Cell::html(link(format!("{link_prefix}{repo}"), repo_title), repo_title)
+}
@@ -1,6 +1,128 @@
+pub mod achievements;
+pub mod bench;
+pub mod calendar;
+pub mod chart;
 pub mod clear_cache;
+pub mod compare;
+pub mod competitive;
 pub mod day;
+pub mod hall_of_fame;
 pub mod join;
 pub mod leaderboard;
+pub mod member;
+pub mod next;
+pub mod prefs;
+pub mod rank_history;
+pub mod review;
 pub mod solutions;
+pub mod stats;
+pub mod streaks;
+pub mod teams;
 pub mod user;
+
+#[cfg(test)]
+pub mod testing {
+    use chrono::{DateTime, Utc};
+
+    use crate::{
+        aoc::{
+            day::AocDay,
+            models::{
+                testing::{leaderboard as from_members, member},
+                PrivateLeaderboard,
+            },
+        },
+        config::{testing::config as from_toml, Config},
+    };
+
+    /// The time of the last leaderboard update in snapshot tests.
+    pub fn last_update() -> DateTime<Utc> {
+        "2024-12-03T12:00:00Z".parse().unwrap()
+    }
+
+    /// Returns a leaderboard of the first two days of 2024, with scores and
+    /// stars computed from the completion times.
+    pub fn leaderboard() -> PrivateLeaderboard {
+        let ts = |day, seconds| AocDay { year: 2024, day }.unlock_datetime().timestamp() + seconds;
+        let members = [
+            (
+                1,
+                Some("Alice"),
+                vec![(1, 300, Some(600)), (2, 400, Some(900))],
+            ),
+            (
+                2,
+                Some("Bob <b>"),
+                vec![(1, 200, Some(1200)), (2, 500, None)],
+            ),
+            (3, None, vec![(1, 1000, None)]),
+            (
+                4,
+                Some("Carol"),
+                vec![(1, 250, Some(700)), (2, 350, Some(650))],
+            ),
+            (5, Some("Dave"), vec![]),
+        ]
+        .map(|(id, name, days)| {
+            let days = days
+                .into_iter()
+                .map(|(day, p1, p2)| (day, ts(day, p1), p2.map(|p2| ts(day, p2))))
+                .collect::<Vec<_>>();
+            let mut member = member(id, &days);
+            member.name = name.map(Into::into);
+            member
+        });
+
+        let mut leaderboard = from_members(&members);
+        for member in &members {
+            let stars = (1..=2)
+                .flat_map(|day| [(day, false), (day, true)])
+                .filter_map(|(day, part2)| {
+                    Some((
+                        member.star_ts(day, part2)?,
+                        leaderboard.star_score(member, day, part2)?,
+                    ))
+                })
+                .collect::<Vec<_>>();
+            let m = leaderboard
+                .members
+                .values_mut()
+                .find(|m| m.id == member.id)
+                .unwrap();
+            m.stars = stars.len() as u32;
+            m.local_score = stars.iter().map(|&(_, score)| score).sum();
+            m.last_star_ts = stars.iter().map(|&(ts, _)| ts).max().unwrap_or_default();
+        }
+        leaderboard
+    }
+
+    /// Returns a config with users for the members of [`leaderboard`].
+    pub fn config() -> Config {
+        from_toml(
+            r#"
+            local_timezone = "+01:00"
+
+            [[users]]
+            aoc = 1
+            matrix = "@alice:example.com"
+            repo = "https://github.com/alice/aoc"
+
+            [[users]]
+            aoc = 2
+            repo = { 2024 = "https://example.com/bob?a=1&b=2" }
+            languages = ["Rust"]
+
+            [[users]]
+            aoc = 4
+            matrix = "@carol:example.com"
+            repo = "https://codeberg.org/carol/advent"
+            languages = ["Python", "Rust"]
+            competitive = false
+
+            [[users]]
+            matrix = "@erin:example.com"
+            repo = "https://gitlab.com/erin/aoc"
+            "#,
+        )
+    }
+}
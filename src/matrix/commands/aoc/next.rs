@@ -0,0 +1,134 @@
+use chrono::{TimeDelta, TimeZone};
+use matrix_sdk::{
+    ruma::events::room::message::{
+        FileInfo, FileMessageEventContent, MessageType, OriginalRoomMessageEvent,
+        RoomMessageEventContent,
+    },
+    Room,
+};
+
+use crate::{
+    aoc::day::AocDay,
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{html_message, RoomExt},
+    },
+    utils::{
+        datetime::{now, DateTimeExt},
+        fmt::fmt_timedelta,
+        html::{html, link},
+        ical,
+        table::{Cell, Table},
+    },
+};
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let ics = match cmd.get_from_kwargs_or_args("ics") {
+        Some("ics" | "true") => true,
+        Some("false") | None => false,
+        Some(_) => return send_error(&room, event, "Failed to parse argument 'ics'").await,
+    };
+
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let now = now();
+    let next = AocDay::next();
+    let schedule = (next.day..=25)
+        .map(|day| AocDay {
+            year: next.year,
+            day,
+        })
+        .collect::<Vec<_>>();
+
+    if ics {
+        return send_ics(event, room, next.year, &schedule).await;
+    }
+
+    let timezone = context.timezone(&event.sender).await;
+    let fmt_dt = |day: AocDay| {
+        timezone
+            .from_utc_datetime(&day.unlock_datetime().naive_utc())
+            .format_ymd_hms_z()
+    };
+
+    let link_prefix = &context.config.matrix.link_prefix;
+    let mut table = Table::new(["Day", "Unlock", "Countdown"]);
+    for day in schedule {
+        let title = format!("{}/{:02}", day.year, day.day);
+        table.push([
+            Cell::html(link(format!("{link_prefix}{}", day.url()), &title), title),
+            Cell::new(fmt_dt(day)),
+            Cell::new(fmt_timedelta(day.unlock_datetime() - now)),
+        ]);
+    }
+
+    let AocDay { year, day } = next;
+    let out = html!(
+        r#"
+<p>The puzzles of <b>Advent of Code {year} Day {day}</b> will unlock in <b>{countdown}</b> ({unlock})</p>
+{table}
+"#,
+        year,
+        day,
+        countdown = fmt_timedelta(next.unlock_datetime() - now),
+        unlock = fmt_dt(next),
+        table = table.render(format)
+    );
+
+    room.reply_to(event, html_message(out)).await?;
+
+    Ok(())
+}
+
+async fn send_ics(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    year: i32,
+    schedule: &[AocDay],
+) -> anyhow::Result<()> {
+    let events = schedule
+        .iter()
+        .map(|&day| ical::Event {
+            uid: format!("aoc-{}-{:02}@aocbot", day.year, day.day),
+            start: day.unlock_datetime(),
+            duration: TimeDelta::hours(1),
+            summary: format!("Advent of Code {} Day {}", day.year, day.day),
+            url: Some(day.url()),
+        })
+        .collect::<Vec<_>>();
+    let calendar = ical::calendar("Advent of Code", &events);
+
+    let mime = mime_guess::from_ext("ics").first_or_octet_stream();
+    let size = calendar.len();
+    let response = room
+        .client()
+        .media()
+        .upload(&mime, calendar.into_bytes(), None)
+        .await?;
+
+    let filename = format!("aoc-{year}.ics");
+    let mut info = FileInfo::new();
+    info.mimetype = Some(mime.to_string());
+    info.size = size.try_into().ok();
+    let mut file =
+        FileMessageEventContent::plain(filename.clone(), response.content_uri).info(Box::new(info));
+    file.filename = Some(filename);
+
+    room.reply_to(event, RoomMessageEventContent::new(MessageType::File(file)))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use matrix_sdk::{
+    ruma::events::{
+        reaction::ReactionEventContent, relation::Annotation,
+        room::message::OriginalRoomMessageEvent,
+    },
+    Room,
+};
+
+use crate::{
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{html_message, RoomExt},
+    },
+    prefs::DmSubscription,
+    utils::{
+        html::{html, Html},
+        serde::via_string::ViaString,
+    },
+};
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let Some(setting) = cmd.args.pop_front() else {
+        return list(event, room, context).await;
+    };
+
+    let value = cmd.get_from_kwargs_or_args("value");
+    let reset = value == Some("reset");
+    let mut prefs = context.prefs.get(&event.sender).await;
+    match setting {
+        "timezone" | "tz" => {
+            prefs.timezone = match value.map(|v| v.parse().ok()) {
+                _ if reset => None,
+                Some(Some(tz)) => Some(ViaString(tz)),
+                Some(None) => {
+                    return send_error(&room, event, "Failed to parse argument 'value'").await
+                }
+                None => return send_error(&room, event, "Argument 'value' is required").await,
+            };
+        }
+        "format" => {
+            prefs.format = match value.map(|v| v.parse().ok()) {
+                _ if reset => None,
+                Some(Some(format)) => Some(format),
+                Some(None) => {
+                    return send_error(&room, event, "Failed to parse argument 'value'").await
+                }
+                None => return send_error(&room, event, "Argument 'value' is required").await,
+            };
+        }
+        "ping" => {
+            prefs.ping = match value {
+                _ if reset => None,
+                Some("on") => Some(true),
+                Some("off") => Some(false),
+                Some(_) => {
+                    return send_error(&room, event, "Failed to parse argument 'value'").await
+                }
+                None => return send_error(&room, event, "Argument 'value' is required").await,
+            };
+        }
+        "language" | "lang" => {
+            prefs.language = match value {
+                _ if reset => None,
+                Some(language) => Some(language.to_owned()),
+                None => return send_error(&room, event, "Argument 'value' is required").await,
+            };
+        }
+        "dm" if !context.users.by_matrix.contains_key(&event.sender) => {
+            return send_error(
+                &room,
+                event,
+                "Direct messages are only available to configured users",
+            )
+            .await
+        }
+        "dm" if reset => prefs.dm.clear(),
+        "dm" => {
+            let subscription = match value.map(|v| v.parse::<DmSubscription>().ok()) {
+                Some(Some(s)) => s,
+                Some(None) => {
+                    return send_error(&room, event, "Failed to parse argument 'value'").await
+                }
+                None => return send_error(&room, event, "Argument 'value' is required").await,
+            };
+            match cmd.get_from_kwargs_or_args("state") {
+                Some("on") | None => prefs.dm.insert(subscription),
+                Some("off") => prefs.dm.remove(&subscription),
+                Some(_) => {
+                    return send_error(&room, event, "Failed to parse argument 'state'").await
+                }
+            };
+        }
+        _ => return send_error(&room, event, "Failed to parse argument 'setting'").await,
+    };
+
+    context.prefs.set(&event.sender, prefs).await?;
+
+    room.send(ReactionEventContent::new(Annotation::new(
+        event.event_id.clone(),
+        "✅️".into(),
+    )))
+    .await?;
+
+    Ok(())
+}
+
+async fn list(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+) -> anyhow::Result<()> {
+    let user = &event.sender;
+    let prefs = context.prefs.get(user).await;
+    let config_user = context.users.by_matrix.get(user);
+
+    let fmt_default = |set: bool| {
+        if set {
+            Html::default()
+        } else {
+            html!(" <i>(default)</i>")
+        }
+    };
+
+    let timezone = context.timezone(user).await;
+    let format = context.table_format(room.room_id(), user).await;
+    let ping = if context.ping(user).await {
+        "on"
+    } else {
+        "off"
+    };
+    let languages = match config_user {
+        Some(u) => context.languages(u).await,
+        None => prefs.language.iter().cloned().collect(),
+    };
+    let dm = DmSubscription::ALL
+        .into_iter()
+        .map(|s| {
+            let state = if prefs.dm.contains(&s) { "on" } else { "off" };
+            format!("{s}: {state}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let out = html!(
+        r#"
+<h3>Preferences of {user}</h3>
+<ul>
+<li><b>Timezone:</b> {timezone}{timezone_default}</li>
+<li><b>Format:</b> {format}{format_default}</li>
+<li><b>Ping:</b> {ping}{ping_default}</li>
+<li><b>Language:</b> {languages}{language_default}</li>
+<li><b>Direct messages:</b> {dm}</li>
+</ul>
+"#,
+        user,
+        timezone,
+        timezone_default = fmt_default(prefs.timezone.is_some()),
+        format,
+        format_default = fmt_default(prefs.format.is_some()),
+        ping,
+        ping_default = fmt_default(prefs.ping.is_some()),
+        languages = languages.join(", "),
+        language_default = fmt_default(prefs.language.is_some()),
+        dm,
+    );
+
+    room.reply_to(event, html_message(out)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use chrono::TimeZone;
+use matrix_sdk::{ruma::events::room::message::OriginalRoomMessageEvent, Room};
+
+use super::user::{find_member, find_member_by_matrix};
+use crate::{
+    aoc::{day::AocDay, stats::rank_history},
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{html_message, RoomExt},
+    },
+    utils::{
+        datetime::DateTimeExt,
+        fmt::{fmt_rank, fmt_sparkline},
+        html::html,
+        table::{Cell, Table},
+    },
+};
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let most_recent = AocDay::most_recent();
+
+    let user = cmd.get_from_kwargs_or_args("user");
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
+        y.parse()
+            .ok()
+            .filter(|y| (2015..=most_recent.year).contains(y))
+    }) {
+        Some(Some(y)) => y,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
+        None => most_recent.year,
+    };
+
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let (leaderboard, last_update) = context.aoc_client.get_private_leaderboard(year).await?;
+    let last_update = context
+        .timezone(&event.sender)
+        .await
+        .from_utc_datetime(&last_update.naive_utc())
+        .format_ymd_hms_z();
+
+    let Some(member) = (match user {
+        Some(name) => find_member(context, &leaderboard, name, || {
+            event.content.mentions.as_ref()?.user_ids.first().cloned()
+        }),
+        None => find_member_by_matrix(context, &leaderboard, &event.sender),
+    }) else {
+        return send_error(&room, event, "User not found").await;
+    };
+
+    let days = if year == most_recent.year {
+        most_recent.day
+    } else {
+        25
+    };
+
+    let competitive = context.competitive().await;
+    if !competitive(member.id) {
+        return send_error(&room, event, "User is not competitive").await;
+    }
+
+    let ranked = leaderboard.filtered(competitive);
+    let history = rank_history(&ranked, year, days)
+        .remove(&member.id)
+        .unwrap_or_default();
+    let ranks = history
+        .iter()
+        .map(|s| s.map(|s| s.rank))
+        .collect::<Vec<_>>();
+    let worst = ranked.members.values().filter(|m| m.stars > 0).count();
+
+    let mut table = Table::new(["Day", "Rank", "Change", "Local Score"]);
+
+    // (day, change), where positive changes are climbs
+    let mut changes = Vec::new();
+    for (i, snapshot) in history.iter().enumerate() {
+        let day = i + 1;
+        let Some(snapshot) = snapshot else {
+            table.push([Cell::new(day), Cell::new(""), Cell::new(""), Cell::new("")]);
+            continue;
+        };
+
+        let change = match i.checked_sub(1).and_then(|j| history[j]) {
+            Some(prev) => {
+                let change = prev.rank as i64 - snapshot.rank as i64;
+                changes.push((day, change));
+                match change {
+                    0 => Cell::new(""),
+                    1.. => Cell::html(
+                        html!(
+                            r##"<span data-mx-color="#00cc00">▲ {change}</span>"##,
+                            change
+                        ),
+                        format!("▲ {change}"),
+                    ),
+                    _ => Cell::html(
+                        html!(
+                            r##"<span data-mx-color="#cc0000">▼ {change}</span>"##,
+                            change = -change
+                        ),
+                        format!("▼ {}", -change),
+                    ),
+                }
+            }
+            None => Cell::new(""),
+        };
+
+        table.push([
+            Cell::new(day),
+            Cell::new(fmt_rank(snapshot.rank)),
+            change,
+            Cell::new(snapshot.local_score),
+        ]);
+    }
+
+    let mut out = html!(
+        r#"
+<h3>Rank History of {name} (Advent of Code {year})</h3>
+<p><code>{sparkline}</code></p>
+{table}
+"#,
+        name = member.display_name(),
+        year,
+        sparkline = fmt_sparkline(&ranks, worst),
+        table = table.render(format)
+    );
+
+    if let Some(&(day, change)) = changes
+        .iter()
+        .filter(|(_, c)| *c > 0)
+        .max_by_key(|(_, c)| *c)
+    {
+        out.push(html!(
+            "<p><b>Biggest climb:</b> {change} ranks after day {day}</p>",
+            change,
+            day
+        ));
+    }
+    if let Some(&(day, change)) = changes
+        .iter()
+        .filter(|(_, c)| *c < 0)
+        .min_by_key(|(_, c)| *c)
+    {
+        out.push(html!(
+            "<p><b>Biggest drop:</b> {change} ranks after day {day}</p>",
+            change = -change,
+            day
+        ));
+    }
+
+    out.push(html!("<sup>Last update: {last_update}</sup>", last_update));
+
+    room.reply_to(event, html_message(out)).await?;
+
+    Ok(())
+}
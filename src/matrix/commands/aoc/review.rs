@@ -0,0 +1,122 @@
+use matrix_sdk::{
+    ruma::events::{
+        reaction::ReactionEventContent, relation::Annotation,
+        room::message::OriginalRoomMessageEvent,
+    },
+    Room,
+};
+
+use crate::{
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{dm_room, html_message, RoomExt},
+    },
+    review::ReviewStatus,
+    utils::html::html,
+};
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    if !context.config.matrix.admin_ids.contains(&event.sender) {
+        return send_error(&room, event, "Permission denied").await;
+    }
+
+    let status = match cmd.args.pop_front() {
+        Some("list") | None => return list(event, room, context).await,
+        Some("dismiss") => ReviewStatus::Dismissed,
+        Some("noncompetitive" | "nc") => ReviewStatus::NonCompetitive,
+        Some(_) => return send_error(&room, event, "Failed to parse argument 'action'").await,
+    };
+
+    let id = match cmd
+        .get_from_kwargs_or_args("id")
+        .map(|x| x.trim_start_matches('#').parse().ok())
+    {
+        Some(Some(x)) => x,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'id'").await,
+        None => return send_error(&room, event, "Argument 'id' is required").await,
+    };
+
+    let Some(flag) = context.review.resolve(id, status).await? else {
+        return send_error(&room, event, "Unknown or already resolved flag").await;
+    };
+
+    if status == ReviewStatus::NonCompetitive {
+        let member = context.users.canonical_aoc_id(flag.solve.member);
+        context.competitive.set(member, false).await?;
+        // also resolve the flags of the other AoC accounts of the same person
+        for other in context.review.open().await {
+            if context.users.canonical_aoc_id(other.solve.member) == member {
+                context.review.resolve(other.id, status).await?;
+            }
+        }
+    }
+
+    room.send(ReactionEventContent::new(Annotation::new(
+        event.event_id.clone(),
+        "✅️".into(),
+    )))
+    .await?;
+
+    Ok(())
+}
+
+async fn list(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+) -> anyhow::Result<()> {
+    let flags = context.review.open().await;
+
+    let mut out = html!(
+        r#"
+<h3>Review Queue</h3>
+<table>
+<tr> <th>ID</th> <th>Year</th> <th>AoC Name</th> <th>Reason</th> </tr>
+"#
+    );
+
+    for flag in flags {
+        let id = flag.id;
+        let year = flag.solve.year;
+        let name = flag.solve.name;
+        let reason = flag.solve.reason.description();
+
+        out.push(html!(
+            r#"
+<tr>
+    <td><code>#{id}</code></td>
+    <td>{year}</td>
+    <td>{name}</td>
+    <td>{reason}</td>
+</tr>
+"#,
+            id,
+            year,
+            name,
+            reason
+        ));
+    }
+
+    out.push(html!("</table>"));
+
+    // the queue names members and is only shown to admins in direct messages
+    let dm = dm_room(&room.client(), &event.sender).await?;
+    if dm.room_id() == room.room_id() {
+        room.reply_to(event, html_message(out)).await?;
+    } else {
+        dm.send(html_message(out)).await?;
+        room.send(ReactionEventContent::new(Annotation::new(
+            event.event_id.clone(),
+            "✅️".into(),
+        )))
+        .await?;
+    }
+
+    Ok(())
+}
@@ -1,10 +1,21 @@
-use std::{cmp::Reverse, collections::HashMap, fmt::Write};
+use std::{cmp::Reverse, collections::HashMap};
 
+use chrono::{FixedOffset, TimeZone};
 use matrix_sdk::{ruma::events::room::message::OriginalRoomMessageEvent, Room};
 
+use super::member::{MatrixUser, MemberView};
 use crate::{
-    aoc::{day::AocDay, models::PrivateLeaderboardMember},
-    matrix::utils::{html_message, RoomExt},
+    aoc::{day::AocDay, models::PrivateLeaderboard},
+    config::{Config, User},
+    forge::RepoMetadata,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{html_message, RoomExt},
+    },
+    utils::{
+        html::{html, Html},
+        table::{Cell, Table, TableFormat},
+    },
     Context,
 };
 
@@ -12,79 +23,304 @@ pub async fn invoke(
     event: &OriginalRoomMessageEvent,
     room: Room,
     context: &Context,
+    mut cmd: ParsedCommand<'_>,
 ) -> anyhow::Result<()> {
-    let aoc_users = context
-        .aoc_client
-        .get_private_leaderboard(AocDay::most_recent().year)
-        .await?
-        .0
-        .members
-        .into_values()
-        .map(|u| (u.id, u))
-        .collect::<HashMap<_, _>>();
-
-    let mut solutions = String::from(
-        r#"
-<h3>Advent of Code Solution Repositories</h3>
-<table>
-<tr> <th>AoC Name</th> <th>Matrix User</th> <th>Repository</th> </tr>
-"#,
-    );
+    let most_recent_year = AocDay::most_recent().year;
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
+        y.parse()
+            .ok()
+            .filter(|y| (2015..=most_recent_year).contains(y))
+    }) {
+        Some(Some(y)) => y,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
+        None => most_recent_year,
+    };
+
+    let lang = cmd.get_from_kwargs_or_args("lang");
+
+    let limit = match cmd
+        .get_from_kwargs_or_args("rows")
+        .map(|y| y.parse().ok().filter(|x| (0..=200).contains(x)))
+    {
+        Some(Some(x)) => x,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'rows'").await,
+        None => context.config.aoc.leaderboard_rows,
+    };
+
+    let offset = match cmd
+        .get_from_kwargs_or_args("offset")
+        .map(|y| y.parse().ok().filter(|x| (0..=200).contains(x)))
+    {
+        Some(Some(x)) => x,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'offset'").await,
+        None => 0,
+    };
+
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
 
+    let (leaderboard, _) = context.aoc_client.get_private_leaderboard(year).await?;
+
+    let mut metadata = HashMap::new();
+    for repo in context.config.users.iter().filter_map(|u| u.repo.get(year)) {
+        if let Some(m) = context.forge.get(repo).await {
+            metadata.insert(repo.to_owned(), m);
+        }
+    }
+
+    let mut users = context.config.users.clone();
+    for user in &mut users {
+        user.languages = context.languages(user).await;
+    }
+
+    let mut view = view(&leaderboard, &users, &metadata, year, lang, limit, offset);
+    for row in &mut view.rows {
+        row.member.resolve_matrix_name(&room).await;
+    }
+    let timezone = context.timezone(&event.sender).await;
+    let solutions = render(&view, &context.config, format, timezone);
+
+    room.reply_to(event, html_message(solutions)).await?;
+
+    Ok(())
+}
+
+pub struct SolutionsView {
+    pub year: i32,
+    pub lang: Option<String>,
+    /// The number of repositories per language, most common first.
+    pub languages: Vec<(String, usize)>,
+    pub rows: Vec<SolutionRow>,
+    pub offset: usize,
+    /// The number of repositories matching the language filter.
+    pub total: usize,
+}
+
+pub struct SolutionRow {
+    pub member: MemberView,
+    pub languages: Vec<String>,
+    pub metadata: Option<RepoMetadata>,
+}
+
+/// List the solution repositories of the given year, optionally filtered by
+/// language, and select the requested slice. `metadata` maps repositories to
+/// the metadata reported by their forge.
+pub fn view(
+    leaderboard: &PrivateLeaderboard,
+    users: &[User],
+    metadata: &HashMap<String, RepoMetadata>,
+    year: i32,
+    lang: Option<&str>,
+    limit: usize,
+    offset: usize,
+) -> SolutionsView {
     let mut rows = Vec::new();
 
-    for user in &context.config.users {
-        let Some(repo) = &user.repo else { continue };
+    for user in users {
+        let Some(repo) = user.repo.get(year) else {
+            continue;
+        };
 
-        let aoc_user = user.aoc.and_then(|id| aoc_users.get(&id));
+        let aoc_user = leaderboard
+            .members
+            .values()
+            .filter(|m| user.aoc.contains(&m.id))
+            .min();
 
         let name = aoc_user.map(|u| u.display_name()).unwrap_or_default();
 
-        let matrix_name = user
-            .matrix
-            .as_ref()
-            .map(|m| m.matrix_to_uri().to_string())
-            .unwrap_or_default();
+        let metadata = metadata.get(repo).cloned();
 
-        let repo_title = context
-            .config
-            .aoc
-            .repo_rules
-            .match_and_replace(repo)
-            .map(|m| m.replacement);
+        // fall back to the primary language reported by the forge
+        let languages = if user.languages.is_empty() {
+            metadata.iter().flat_map(|m| m.language.clone()).collect()
+        } else {
+            user.languages.clone()
+        };
 
-        rows.push((aoc_user, name, matrix_name, repo, repo_title));
+        rows.push((
+            aoc_user,
+            SolutionRow {
+                member: MemberView {
+                    name,
+                    matrix: user.matrix.clone().map(MatrixUser::new),
+                    repo: Some(repo.into()),
+                },
+                languages,
+                metadata,
+            },
+        ));
     }
 
-    rows.sort_unstable_by(|a, b| {
-        fn key<'a>(
-            aoc_user: Option<&'a PrivateLeaderboardMember>,
-            name: &'a str,
-        ) -> impl Ord + use<'a> {
-            (Reverse(aoc_user.map(Reverse)), name)
-        }
-        key(a.0, &a.1).cmp(&key(b.0, &b.1))
+    rows.sort_unstable_by(|(a, a_row), (b, b_row)| {
+        (Reverse(a.map(Reverse)), &a_row.member.name)
+            .cmp(&(Reverse(b.map(Reverse)), &b_row.member.name))
     });
 
-    for (_, name, matrix_name, repo, repo_title) in rows {
-        let repo_title = repo_title.as_deref().unwrap_or(repo);
-        let link_prefix = &context.config.matrix.link_prefix;
-        write!(
-            &mut solutions,
-            r#"
-<tr>
-    <td>{name}</td>
-    <td>{matrix_name}</td>
-    <td><a href="{link_prefix}{repo}">{repo_title}</a></td>
-</tr>
-"#
-        )
-        .unwrap();
+    let mut languages = HashMap::<_, usize>::new();
+    for lang in rows.iter().flat_map(|(_, r)| &r.languages) {
+        *languages.entry(lang.as_str()).or_default() += 1;
     }
+    let mut languages = languages
+        .into_iter()
+        .map(|(lang, count)| (lang.to_owned(), count))
+        .collect::<Vec<_>>();
+    languages.sort_unstable_by(|(a, a_count), (b, b_count)| {
+        (Reverse(a_count), a).cmp(&(Reverse(b_count), b))
+    });
 
-    solutions.push_str("</table>");
+    if let Some(lang) = lang {
+        rows.retain(|(_, r)| r.languages.iter().any(|l| l.eq_ignore_ascii_case(lang)));
+    }
 
-    room.reply_to(event, html_message(solutions)).await?;
+    SolutionsView {
+        year,
+        lang: lang.map(Into::into),
+        languages,
+        total: rows.len(),
+        rows: rows
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(_, r)| r)
+            .collect(),
+        offset,
+    }
+}
 
-    Ok(())
+fn render(
+    view: &SolutionsView,
+    config: &Config,
+    format: TableFormat,
+    timezone: FixedOffset,
+) -> Html {
+    let SolutionsView {
+        year,
+        lang,
+        languages,
+        rows,
+        offset,
+        total,
+    } = view;
+
+    let languages = if languages.is_empty() {
+        Html::default()
+    } else {
+        let languages = languages
+            .iter()
+            .map(|(lang, count)| format!("{count} {lang}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        html!("<p>Languages: {languages}</p>", languages)
+    };
+
+    let lang_title = lang.as_ref().map(|l| format!(", {l}")).unwrap_or_default();
+    let mut table = Table::new([
+        "AoC Name",
+        "Matrix User",
+        "Repository",
+        "Languages",
+        "Last Push",
+    ]);
+
+    for row in rows {
+        let last_push = row
+            .metadata
+            .as_ref()
+            .map(|m| fmt_last_push(m, *year, timezone))
+            .unwrap_or_default();
+        table.push(
+            row.member
+                .cells(config, false)
+                .into_iter()
+                .chain([Cell::new(row.languages.join(", ")), Cell::new(last_push)]),
+        );
+    }
+
+    let mut solutions = html!(
+        r#"
+<h3>Advent of Code Solution Repositories ({year}{lang_title})</h3>
+{languages}
+{table}
+"#,
+        year,
+        lang_title,
+        languages,
+        table = table.render(format)
+    );
+
+    let first = (offset + 1).min(*total);
+    let last = (offset + rows.len()).min(*total);
+    solutions.push(html!(
+        "<sup>Showing {first}-{last} of {total}</sup>",
+        first,
+        last,
+        total
+    ));
+
+    solutions
+}
+
+/// Format the date of the last push to a repository and flag it if the
+/// repository has not been updated during the current event.
+pub fn fmt_last_push(metadata: &RepoMetadata, year: i32, timezone: FixedOffset) -> String {
+    let Some(last_push) = metadata.last_push else {
+        return String::new();
+    };
+    let date = timezone
+        .from_utc_datetime(&last_push.naive_utc())
+        .format("%Y-%m-%d");
+    if year == AocDay::most_recent().year && metadata.is_stale(year) {
+        format!("{date} 💤")
+    } else {
+        date.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        matrix::commands::aoc::testing::{config, last_update, leaderboard},
+        utils::datetime::set_now,
+    };
+
+    #[test]
+    fn snapshot() {
+        let _guard = set_now(last_update());
+        let config = config();
+        let metadata = HashMap::from([(
+            "https://codeberg.org/carol/advent".into(),
+            RepoMetadata {
+                language: Some("Python".into()),
+                last_push: Some("2024-11-01T12:00:00Z".parse().unwrap()),
+            },
+        )]);
+
+        let v = super::view(&leaderboard(), &config.users, &metadata, 2024, None, 20, 0);
+        insta::assert_snapshot!(
+            "all",
+            render(&v, &config, TableFormat::Table, config.local_timezone).as_str()
+        );
+
+        let v = super::view(
+            &leaderboard(),
+            &config.users,
+            &metadata,
+            2024,
+            Some("rust"),
+            1,
+            1,
+        );
+        insta::assert_snapshot!(
+            "rust",
+            render(&v, &config, TableFormat::Compact, config.local_timezone).as_str()
+        );
+    }
 }
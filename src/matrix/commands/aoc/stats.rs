@@ -0,0 +1,227 @@
+use std::cmp::Reverse;
+
+use chrono::TimeZone;
+use matrix_sdk::{ruma::events::room::message::OriginalRoomMessageEvent, Room};
+
+use crate::{
+    aoc::{
+        day::AocDay,
+        stats::{DayStats, SolveTimes},
+    },
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{html_message, RoomExt},
+    },
+    utils::{
+        datetime::DateTimeExt,
+        fmt::fmt_timedelta,
+        html::{html, link, Html},
+        table::{Cell, Table, TableFormat},
+    },
+};
+
+const HISTOGRAM_WIDTH: usize = 20;
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let year_given = cmd.kwargs.contains_key("year");
+    let day = match cmd
+        .get_from_kwargs_or_args("day")
+        .map(|d| d.parse().ok().filter(|d| (1..=25).contains(d)))
+        .or_else(|| {
+            AocDay::current()
+                .filter(|_| !year_given)
+                .map(|d| Some(d.day))
+        }) {
+        Some(Some(d)) => Some(d),
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'day'").await,
+        None => None,
+    };
+
+    let most_recent = AocDay::most_recent();
+    let most_recent_year = most_recent.year;
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
+        y.parse()
+            .ok()
+            .filter(|y| (2015..=most_recent_year).contains(y))
+    }) {
+        Some(Some(y)) => y,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
+        None => most_recent_year,
+    };
+
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let (leaderboard, last_update) = context.aoc_client.get_private_leaderboard(year).await?;
+
+    let timezone = context.timezone(&event.sender).await;
+    let last_update = timezone
+        .from_utc_datetime(&last_update.naive_utc())
+        .format_ymd_hms_z();
+
+    let mut out = match day {
+        Some(day) => {
+            let stats = DayStats::compute(&leaderboard, AocDay { year, day }, timezone);
+            fmt_day_stats(&stats, format)
+        }
+        None => {
+            let days = if year == most_recent.year {
+                most_recent.day
+            } else {
+                25
+            };
+            let stats = (1..=days)
+                .map(|day| DayStats::compute(&leaderboard, AocDay { year, day }, timezone))
+                .collect();
+            fmt_year_stats(year, stats, &context.config.matrix.link_prefix, format)
+        }
+    };
+
+    out.push(html!("<sup>Last update: {last_update}</sup>", last_update));
+
+    room.reply_to(event, html_message(out)).await?;
+
+    Ok(())
+}
+
+fn fmt_day_stats(stats: &DayStats, format: TableFormat) -> Html {
+    let AocDay { year, day } = stats.day;
+
+    let mut table = Table::new(["Part", "Finishers", "Fastest", "Median", "90th Percentile"]);
+    for (part, times) in [(1, stats.p1), (2, stats.p2)] {
+        table.push(match times {
+            Some(SolveTimes {
+                finishers,
+                min,
+                median,
+                p90,
+            }) => [
+                Cell::new(part),
+                Cell::new(finishers),
+                Cell::new(fmt_timedelta(min)),
+                Cell::new(fmt_timedelta(median)),
+                Cell::new(fmt_timedelta(p90)),
+            ],
+            None => [
+                Cell::new(part),
+                Cell::new(0),
+                Cell::new(""),
+                Cell::new(""),
+                Cell::new(""),
+            ],
+        });
+    }
+    let gap = stats
+        .gap_median
+        .map(|gap| fmt_timedelta(gap).to_string())
+        .unwrap_or_default();
+
+    let mut out = html!(
+        r#"
+<h3>Statistics (Advent of Code {year}/{day})</h3>
+{table}
+<p>Median time between part one and part two: <b>{gap}</b></p>
+"#,
+        year,
+        day = format!("{day:02}"),
+        table = table.render(format),
+        gap
+    );
+
+    let max = stats
+        .hours
+        .iter()
+        .map(|&(p1, p2)| p1.max(p2))
+        .max()
+        .unwrap_or(0);
+    let first = stats.hours.iter().position(|&h| h != (0, 0));
+    let last = stats.hours.iter().rposition(|&h| h != (0, 0));
+    if let (Some(first), Some(last)) = (first, last) {
+        let bar = |n: usize, color: &str| {
+            let bar = "█".repeat((n * HISTOGRAM_WIDTH).div_ceil(max));
+            Cell::html(
+                html!(
+                    r#"<span data-mx-color="{color}">{bar}</span> {n}"#,
+                    color,
+                    bar,
+                    n
+                ),
+                format!("{bar} {n}"),
+            )
+        };
+        let mut table = Table::new(["Hour", "Part 1", "Part 2"]);
+        for (hour, &(p1, p2)) in stats.hours.iter().enumerate().take(last + 1).skip(first) {
+            table.push([
+                Cell::new(format!("{hour:02}:00")),
+                bar(p1, "#9999cc"),
+                bar(p2, "#ffff66"),
+            ]);
+        }
+        out.push(table.render(format));
+    }
+
+    out
+}
+
+fn fmt_year_stats(
+    year: i32,
+    mut stats: Vec<DayStats>,
+    link_prefix: &str,
+    format: TableFormat,
+) -> Html {
+    stats.sort_by_key(|s| (Reverse(s.difficulty()), s.day.day));
+
+    let mut table = Table::new([
+        "Rank",
+        "Day",
+        "Finishers",
+        "Median Part 1",
+        "Median Part 2",
+        "Median Gap",
+    ]);
+    for (i, stats) in stats.into_iter().enumerate() {
+        let day = stats.day.day;
+        let url = stats.day.url();
+        let fmt_median = |times: Option<SolveTimes>| {
+            times
+                .map(|t| fmt_timedelta(t.median).to_string())
+                .unwrap_or_default()
+        };
+        let p1_finishers = stats.p1.map(|t| t.finishers).unwrap_or(0);
+        let p2_finishers = stats.p2.map(|t| t.finishers).unwrap_or(0);
+        let gap = stats
+            .gap_median
+            .map(|gap| fmt_timedelta(gap).to_string())
+            .unwrap_or_default();
+
+        table.push([
+            Cell::new(i + 1),
+            Cell::html(link(format!("{link_prefix}{url}"), day), day),
+            Cell::new(format!("{p2_finishers}/{p1_finishers}")),
+            Cell::new(fmt_median(stats.p1)),
+            Cell::new(fmt_median(stats.p2)),
+            Cell::new(gap),
+        ]);
+    }
+
+    html!(
+        r#"
+<h3>Hardest Puzzles (Advent of Code {year})</h3>
+{table}
+"#,
+        year,
+        table = table.render(format)
+    )
+}
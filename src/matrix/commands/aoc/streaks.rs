@@ -0,0 +1,165 @@
+use std::cmp::Reverse;
+
+use chrono::TimeZone;
+use matrix_sdk::{ruma::events::room::message::OriginalRoomMessageEvent, Room};
+
+use crate::{
+    aoc::{
+        day::AocDay,
+        stats::{competition_ranks, Streaks},
+    },
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{html_message, RoomExt},
+    },
+    utils::{
+        datetime::DateTimeExt,
+        fmt::fmt_rank,
+        html::html,
+        table::{Cell, Table},
+    },
+};
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    let most_recent = AocDay::most_recent();
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
+        y.parse()
+            .ok()
+            .filter(|y| (2015..=most_recent.year).contains(y))
+    }) {
+        Some(Some(y)) => y,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
+        None => most_recent.year,
+    };
+
+    let rows = match cmd
+        .get_from_kwargs_or_args("rows")
+        .map(|y| y.parse().ok().filter(|x| (0..=200).contains(x)))
+    {
+        Some(Some(x)) => x,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'rows'").await,
+        None => context.config.aoc.leaderboard_rows,
+    };
+
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let (leaderboard, last_update) = context.aoc_client.get_private_leaderboard(year).await?;
+    let last_update = context
+        .timezone(&event.sender)
+        .await
+        .from_utc_datetime(&last_update.naive_utc())
+        .format_ymd_hms_z();
+
+    let days = if year == most_recent.year {
+        most_recent.day
+    } else {
+        25
+    };
+
+    let competitive = context.competitive().await;
+    let mut streaks = Vec::new();
+    for m in leaderboard.members.values() {
+        let s = Streaks::compute(m, year, days, context.config.local_timezone);
+        if s.solved.best > 0 {
+            streaks.push((m, s));
+        }
+    }
+    streaks.sort_unstable_by_key(|&(m, s)| {
+        (
+            Reverse(s.solved.current),
+            Reverse(s.unlock_day.current),
+            Reverse(s.solved.best),
+            Reverse(s.unlock_day.best),
+            m,
+        )
+    });
+    let (streaks, non_competitive): (Vec<_>, Vec<_>) =
+        streaks.into_iter().partition(|(m, _)| competitive(m.id));
+
+    let cells = |streaks: Streaks| {
+        let Streaks {
+            solved, unlock_day, ..
+        } = streaks;
+        [
+            Cell::new(solved.current).bold(true),
+            Cell::new(solved.best),
+            Cell::new(unlock_day.current).bold(true),
+            Cell::new(unlock_day.best),
+        ]
+    };
+    let header = [
+        "Streak",
+        "Best Streak",
+        "Unlock Day Streak",
+        "Best Unlock Day Streak",
+    ];
+
+    let mut table = Table::new(["Rank", "AoC Name"].into_iter().chain(header));
+    for (rank, (member, streaks)) in competition_ranks(streaks, |(_, s)| {
+        (
+            s.solved.current,
+            s.unlock_day.current,
+            s.solved.best,
+            s.unlock_day.best,
+        )
+    })
+    .take(rows)
+    {
+        table.push(
+            [Cell::new(fmt_rank(rank)), Cell::new(member.display_name())]
+                .into_iter()
+                .chain(cells(streaks)),
+        );
+    }
+
+    let mut out = html!(
+        r#"
+<h3>Streaks (Advent of Code {year})</h3>
+{table}
+"#,
+        year,
+        table = table.render(format)
+    );
+
+    if !non_competitive.is_empty() {
+        let mut table = Table::new(["AoC Name"].into_iter().chain(header));
+        for (member, streaks) in non_competitive.into_iter().take(rows) {
+            table.push(
+                [Cell::new(member.display_name())]
+                    .into_iter()
+                    .chain(cells(streaks)),
+            );
+        }
+        out.push(html!(
+            r#"
+<h4>Non-Competitive</h4>
+{table}
+"#,
+            table = table.render(format)
+        ));
+    }
+
+    out.push(html!(
+        r#"
+<sup>Last update: {last_update}</sup>
+"#,
+        last_update
+    ));
+
+    room.reply_to(event, html_message(out)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use chrono::TimeZone;
+use matrix_sdk::{ruma::events::room::message::OriginalRoomMessageEvent, Room};
+
+use super::leaderboard::reply_fetch_error;
+use crate::{
+    aoc::{
+        day::AocDay,
+        stats::{competition_ranks, team_scores},
+    },
+    context::Context,
+    matrix::{
+        commands::{parser::ParsedCommand, send_error},
+        utils::{html_message, RoomExt},
+    },
+    utils::{
+        datetime::DateTimeExt,
+        fmt::fmt_rank,
+        html::html,
+        table::{Cell, Table},
+    },
+};
+
+pub async fn invoke(
+    event: &OriginalRoomMessageEvent,
+    room: Room,
+    context: &Context,
+    mut cmd: ParsedCommand<'_>,
+) -> anyhow::Result<()> {
+    if context.config.teams.is_empty() {
+        return send_error(&room, event, "No teams have been configured").await;
+    }
+
+    let most_recent_year = AocDay::most_recent().year;
+    let year = match cmd.get_from_kwargs_or_args("year").map(|y| {
+        y.parse()
+            .ok()
+            .filter(|y| (2015..=most_recent_year).contains(y))
+    }) {
+        Some(Some(y)) => y,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'year'").await,
+        None => most_recent_year,
+    };
+
+    let day = match cmd
+        .get_from_kwargs_or_args("day")
+        .map(|d| d.parse::<u32>().ok().filter(|d| (1..=25).contains(d)))
+    {
+        Some(Some(d)) => Some(d),
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'day'").await,
+        None => None,
+    };
+
+    let average = match cmd.get_from_kwargs_or_args("mode") {
+        Some("sum") | None => false,
+        Some("avg") => true,
+        Some(_) => return send_error(&room, event, "Failed to parse argument 'mode'").await,
+    };
+
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
+
+    let result = context.aoc_client.get_private_leaderboard(year).await;
+    let Some((leaderboard, last_update)) = reply_fetch_error(&room, event, year, result).await?
+    else {
+        return Ok(());
+    };
+    let last_update = context
+        .timezone(&event.sender)
+        .await
+        .from_utc_datetime(&last_update.naive_utc())
+        .format_ymd_hms_z();
+
+    let ranked = leaderboard.filtered(context.competitive().await);
+    let mut scores = team_scores(
+        &ranked,
+        day,
+        |id| context.users.team(id),
+        |id| context.users.canonical_aoc_id(id),
+    );
+    if average {
+        scores.sort_by(|a, b| b.average().total_cmp(&a.average()));
+    } else {
+        scores.sort_by_key(|t| std::cmp::Reverse(t.score));
+    }
+
+    let title = match day {
+        Some(day) => format!("{year}/{day:02}"),
+        None => year.to_string(),
+    };
+    let mut table = Table::new([
+        "Rank",
+        "Team",
+        if average { "Average Score" } else { "Score" },
+        "Stars",
+        "Members",
+    ]);
+    let scores = scores.iter().map(|team| {
+        let score = if average {
+            format!("{:.1}", team.average())
+        } else {
+            team.score.to_string()
+        };
+        (score, team)
+    });
+    // teams whose (rounded) scores are equal share a rank
+    for (rank, (score, team)) in competition_ranks(scores, |(score, _)| score.clone()) {
+        table.push([
+            Cell::new(fmt_rank(rank)),
+            Cell::new(team.name),
+            Cell::new(score).bold(true),
+            Cell::new(team.stars),
+            Cell::new(team.members),
+        ]);
+    }
+
+    let out = html!(
+        r#"
+<h3>Teams (Advent of Code {title})</h3>
+{table}
+<sup>Last update: {last_update}</sup>
+"#,
+        title,
+        table = table.render(format),
+        last_update
+    );
+
+    room.reply_to(event, html_message(out)).await?;
+
+    Ok(())
+}
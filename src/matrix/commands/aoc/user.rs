@@ -1,21 +1,30 @@
-use std::{cmp::Reverse, fmt::Write};
+use std::cmp::Reverse;
 
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
 use matrix_sdk::{
-    ruma::{events::room::message::OriginalRoomMessageEvent, OwnedUserId},
+    ruma::{events::room::message::OriginalRoomMessageEvent, OwnedUserId, UserId},
     Room,
 };
 
+use super::{member::MemberView, solutions::fmt_last_push};
 use crate::{
-    aoc::day::AocDay,
-    context::Context,
+    aoc::{
+        day::AocDay,
+        models::{AocId, PrivateLeaderboard, PrivateLeaderboardMember},
+        stats::{rank_history, Streaks},
+    },
+    config::Config,
+    context::{Context, ContextUsers},
+    forge::RepoMetadata,
     matrix::{
         commands::{parser::ParsedCommand, send_error},
         utils::{error_message, html_message, RoomExt},
     },
     utils::{
         datetime::DateTimeExt,
-        fmt::{fmt_rank, fmt_timedelta},
+        fmt::{fmt_rank, fmt_sparkline, fmt_timedelta},
+        html::{html, link, Html},
+        table::{Cell, Table, TableFormat},
     },
 };
 
@@ -39,195 +48,514 @@ pub async fn invoke(
         None => most_recent_year,
     };
 
-    let (leaderboard, last_update) = context.aoc_client.get_private_leaderboard(year).await?;
+    let format = match cmd
+        .get_from_kwargs_or_args("format")
+        .map(|f| f.parse().ok())
+    {
+        Some(Some(f)) => f,
+        Some(None) => return send_error(&room, event, "Failed to parse argument 'format'").await,
+        None => context.table_format(room.room_id(), &event.sender).await,
+    };
 
-    let last_update = context
-        .config
-        .local_timezone
-        .from_utc_datetime(&last_update.naive_utc())
-        .format_ymd_hms_z();
+    let (leaderboard, last_update) = context.aoc_client.get_private_leaderboard(year).await?;
 
-    let Some(user) = (match user {
-        Some(name) => leaderboard
-            .members
-            .values()
-            .find(|m| {
-                name.parse() == Ok(m.id)
-                    || m.name
-                        .as_ref()
-                        .is_some_and(|n| n.to_lowercase() == name.to_lowercase())
-            })
-            .or_else(|| {
-                context
-                    .users
-                    .by_matrix
-                    .get(
-                        name.parse::<OwnedUserId>()
-                            .ok()
-                            .as_ref()
-                            .or_else(|| event.content.mentions.as_ref()?.user_ids.iter().next())?,
-                    )
-                    .and_then(|u| leaderboard.members.values().find(|m| u.aoc == Some(m.id)))
-            }),
-        None => context
-            .users
-            .by_matrix
-            .get(&event.sender)
-            .and_then(|u| leaderboard.members.values().find(|m| u.aoc == Some(m.id))),
+    let Some(member) = (match user {
+        Some(name) => find_member(context, &leaderboard, name, || {
+            event.content.mentions.as_ref()?.user_ids.first().cloned()
+        }),
+        None => find_member_by_matrix(context, &leaderboard, &event.sender),
     }) else {
         room.reply_to(event, error_message("User not found"))
             .await?;
         return Ok(());
     };
 
-    let name = user.display_name();
-
-    let aoc_id = user.id;
-
-    let matrix = context
-        .users
-        .by_aoc
-        .get(&user.id)
-        .and_then(|u| u.matrix.as_ref())
-        .map(|m| m.matrix_to_uri().to_string())
-        .unwrap_or_default();
-
-    let repo = context
-        .users
-        .by_aoc
-        .get(&user.id)
-        .and_then(|u| u.repo.as_deref())
-        .unwrap_or_default();
-    let repo_title = context
-        .config
-        .aoc
-        .repo_rules
-        .match_and_replace(repo)
-        .map(|m| m.replacement);
-    let repo_title = repo_title.as_deref().unwrap_or(repo);
-
-    let stars = user.stars;
-    let rank = fmt_rank(leaderboard.members.values().filter(|&o| o <= user).count());
-    let local_score = user.local_score;
-    let global_score = user.global_score;
-
     let days = if year == most_recent.year {
         most_recent.day
     } else {
         25
     };
-    let max_stars = days * 2;
-    let progress_percent = stars as f64 / max_stars as f64 * 100.0;
 
-    let link_prefix = &context.config.matrix.link_prefix;
-    let mut out = format!(
-        r#"
-<table>
-    <tr>
-        <th>AoC ID</th>
-        <th>AoC Name</th>
-        <th>Matrix User</th>
-        <th>Repository</th>
-    </tr>
-
-    <tr>
-        <td>{aoc_id}</td>
-        <td>{name}</td>
-        <td>{matrix}</td>
-        <td><a href="{link_prefix}{repo}">{repo_title}</a></td>
-    </tr>
-
-    <tr>
-        <th>Stars</th>
-        <th>Rank</th>
-        <th>Local Score</th>
-        <th>Global Score</th>
-    </tr>
-
-    <tr>
-        <td>{stars}/{max_stars} ({progress_percent:.0}%)</td>
-        <td>{rank}</td>
-        <td>{local_score}</td>
-        <td>{global_score}</td>
-    </tr>
-</table>
-
-<table>
-    <tr>
-        <th>Day</th>
-        <th>Part 1</th>
-        <th>Part 2</th>
-    </tr>
-"#
+    let competitive = context.competitive().await;
+    let mut view = view(
+        &leaderboard,
+        &context.users,
+        competitive,
+        member,
+        year,
+        days,
+        context.config.local_timezone,
     );
 
-    for d in 1..=days {
+    view.member.resolve_matrix_name(&room).await;
+    if let Some(repo) = &view.member.repo {
+        view.repo_metadata = context.forge.get(repo).await;
+    }
+
+    let canonical_id = context.users.canonical_aoc_id(member.id);
+    view.achievements = context
+        .achievements
+        .get(canonical_id)
+        .await
+        .into_iter()
+        .filter(|a| a.year == year)
+        .filter_map(|a| context.config.achievements.iter().find(|c| c.id == a.id))
+        .map(|a| a.name.clone())
+        .collect();
+
+    view.first_bloods = context
+        .first_bloods
+        .get_year(year)
+        .await
+        .into_iter()
+        .filter(|r| r.member == canonical_id)
+        .map(|r| (r.day, r.part2))
+        .collect();
+    view.first_bloods.sort_unstable();
+
+    let timezone = context.timezone(&event.sender).await;
+    let out = render(&view, &context.config, format, timezone, last_update);
+
+    room.reply_to(event, html_message(out)).await?;
+
+    Ok(())
+}
+
+pub struct UserView {
+    pub year: i32,
+    pub id: AocId,
+    pub member: MemberView,
+    /// Filled in from the forge, if available.
+    pub repo_metadata: Option<RepoMetadata>,
+    pub stars: u32,
+    /// The number of stars that could have been collected so far.
+    pub max_stars: u32,
+    /// The rank among competitive members, `None` for non-competitive members.
+    pub rank: Option<usize>,
+    pub local_score: u32,
+    pub global_score: u32,
+    pub streaks: Streaks,
+    /// The completion times and ranks of both parts for each unlocked day.
+    pub days: Vec<[Option<Completion>; 2]>,
+    /// The rank after each unlocked day.
+    pub ranks: Vec<Option<usize>>,
+    /// The number of competitive members with any stars, i.e. the worst
+    /// possible rank.
+    pub worst: usize,
+    /// The names of the achievements unlocked in this year, filled in from the
+    /// store.
+    pub achievements: Vec<String>,
+    /// The `(day, part2)` first bloods in this year, filled in from the store.
+    pub first_bloods: Vec<(u32, bool)>,
+}
+
+#[derive(Clone, Copy)]
+pub struct Completion {
+    pub ts: DateTime<Utc>,
+    /// The number of competitive members that solved the part up to and
+    /// including `ts`, `None` for non-competitive members.
+    pub rank: Option<usize>,
+}
+
+/// Collect the stats of the given member for the first `days` days. Ranks and
+/// scores are computed among the competitive members only.
+pub fn view(
+    leaderboard: &PrivateLeaderboard,
+    users: &ContextUsers,
+    competitive: impl Fn(AocId) -> bool,
+    member: &PrivateLeaderboardMember,
+    year: i32,
+    days: u32,
+    timezone: FixedOffset,
+) -> UserView {
+    let completion = |m: &PrivateLeaderboardMember, day, part2| {
+        let completion = m.completion_day_level.get(&day)?;
+        if part2 {
+            completion.snd.as_ref().map(|c| c.get_star_ts)
+        } else {
+            Some(completion.fst.get_star_ts)
+        }
+    };
+    let part = |day, part2| {
+        let ts = completion(member, day, part2)?;
+        let rank = competitive(member.id).then(|| {
+            leaderboard
+                .members
+                .values()
+                .filter(|m| competitive(m.id))
+                .filter(|m| completion(m, day, part2).map(Reverse) >= Some(Reverse(ts)))
+                .count()
+        });
+        Some(Completion { ts, rank })
+    };
+
+    let ranked = leaderboard.filtered(&competitive);
+    let ranked_member = ranked.members.values().find(|m| m.id == member.id);
+
+    UserView {
+        year,
+        id: member.id,
+        member: MemberView::new(users, member.id, member.display_name(), Some(year)),
+        repo_metadata: None,
+        stars: member.stars,
+        max_stars: days * 2,
+        rank: ranked_member.map(|member| ranked.members.values().filter(|&o| o <= member).count()),
+        local_score: ranked_member.unwrap_or(member).local_score,
+        global_score: member.global_score,
+        streaks: Streaks::compute(member, year, days, timezone),
+        days: (1..=days)
+            .map(|d| [part(d, false), part(d, true)])
+            .collect(),
+        ranks: rank_history(&ranked, year, days)
+            .remove(&member.id)
+            .unwrap_or_else(|| vec![None; days as usize])
+            .into_iter()
+            .map(|s| s.map(|s| s.rank))
+            .collect(),
+        worst: ranked.members.values().filter(|m| m.stars > 0).count(),
+        achievements: Vec::new(),
+        first_bloods: Vec::new(),
+    }
+}
+
+fn render(
+    view: &UserView,
+    config: &Config,
+    format: TableFormat,
+    timezone: FixedOffset,
+    last_update: DateTime<Utc>,
+) -> Html {
+    let UserView {
+        year,
+        id: aoc_id,
+        ref member,
+        ref repo_metadata,
+        stars,
+        max_stars,
+        rank,
+        local_score,
+        global_score,
+        streaks,
+        ref days,
+        ref ranks,
+        worst,
+        ref achievements,
+        ref first_bloods,
+    } = *view;
+
+    let (repo, repo_title) = member
+        .repo
+        .as_deref()
+        .map(|repo| {
+            let repo_title = config
+                .aoc
+                .repo_rules
+                .match_and_replace(repo)
+                .map_or_else(|| repo.to_owned(), |m| m.replacement);
+            let link_prefix = &config.matrix.link_prefix;
+            (
+                link(format!("{link_prefix}{repo}"), &repo_title),
+                repo_title,
+            )
+        })
+        .unwrap_or_default();
+    let repo_metadata = match repo_metadata {
+        Some(metadata) => {
+            let last_push = fmt_last_push(metadata, year, timezone);
+            let mut details = metadata.language.iter().cloned().collect::<Vec<_>>();
+            if !last_push.is_empty() {
+                details.push(last_push);
+            }
+            if details.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", details.join(", "))
+            }
+        }
+        None => String::new(),
+    };
+
+    let rank = match rank {
+        Some(rank) => Cell::new(fmt_rank(rank)),
+        None => Cell::html(html!("<i>non-competitive</i>"), "non-competitive"),
+    };
+    let progress_percent = format!("{:.0}", stars as f64 / max_stars as f64 * 100.0);
+
+    let mut identity = Table::new(["AoC ID", "AoC Name", "Matrix User", "Repository"]);
+    identity.push([
+        Cell::new(aoc_id),
+        Cell::new(&member.name),
+        member
+            .matrix
+            .as_ref()
+            .map_or_else(|| Cell::new(""), |m| Cell::html(m.pill(), &m.name)),
+        Cell::html(
+            html!(
+                "{repo}{repo_metadata}",
+                repo,
+                repo_metadata = &repo_metadata
+            ),
+            format!("{repo_title}{repo_metadata}"),
+        ),
+    ]);
+
+    let mut score = Table::new(["Stars", "Rank", "Local Score", "Global Score"]);
+    score.push([
+        Cell::new(format!("{stars}/{max_stars} ({progress_percent}%)")),
+        rank,
+        Cell::new(local_score),
+        Cell::new(global_score),
+    ]);
+
+    let mut streak = Table::new([
+        "Streak",
+        "Best Streak",
+        "Unlock Day Streak",
+        "Best Unlock Day Streak",
+    ]);
+    streak.push([
+        Cell::new(streaks.solved.current),
+        Cell::new(streaks.solved.best),
+        Cell::new(streaks.unlock_day.current),
+        Cell::new(streaks.unlock_day.best),
+    ]);
+
+    let fmt_dt = |dt: DateTime<Utc>| timezone.from_utc_datetime(&dt.naive_utc()).format_ymd_hms();
+    let mut table = Table::new(["Day", "Part 1", "Part 2", "Rank"]);
+    for ((d, parts), rank) in (1..).zip(days).zip(ranks) {
         let unlock = AocDay { year, day: d }.unlock_datetime();
-        let fmt_dt = |dt: DateTime<Utc>| {
-            context
-                .config
-                .local_timezone
-                .from_utc_datetime(&dt.naive_utc())
-                .format_ymd_hms()
+        let p1 = match parts[0] {
+            Some(Completion { ts, rank }) => {
+                let (ts, time, (rank_html, rank)) =
+                    (fmt_dt(ts), fmt_timedelta(ts - unlock), fmt_part_rank(rank));
+                Cell::html(
+                    html!("{ts} (<b>{time}</b>{rank_html})", ts, time, rank_html),
+                    format!("{ts} ({time}{rank})"),
+                )
+            }
+            None => Cell::new(""),
         };
-        let p1 = user.completion_day_level.get(&d).map(|c| c.fst.get_star_ts);
-        let p2 = user
-            .completion_day_level
-            .get(&d)
-            .and_then(|c| c.snd.as_ref())
-            .map(|c| c.get_star_ts);
-
-        let rank_p1 = leaderboard
-            .members
-            .values()
-            .filter(|m| {
-                m.completion_day_level
-                    .get(&d)
-                    .map(|c| Reverse(c.fst.get_star_ts))
-                    >= p1.map(Reverse)
-            })
-            .count();
-
-        let rank_p2 = leaderboard
-            .members
-            .values()
-            .filter(|m| {
-                m.completion_day_level
-                    .get(&d)
-                    .and_then(|d| d.snd.as_ref())
-                    .map(|c| Reverse(c.get_star_ts))
-                    >= p2.map(Reverse)
-            })
-            .count();
-
-        match (p1, p2) {
-            (None, _) => write!(&mut out, "<tr><td>{d}</td><td></td><td></td></tr>"),
-            (Some(p1), None) => write!(
-                &mut out,
-                "<tr><td>{d}</td><td>{} (<b>{}</b>, <b>{}</b>)</td><td></td></tr>",
-                fmt_dt(p1),
-                fmt_timedelta(p1 - unlock),
-                fmt_rank(rank_p1),
-            ),
-            (Some(p1), Some(p2)) => write!(
-                &mut out,
-                "<tr><td>{d}</td><td>{} (<b>{}</b>, <b>{}</b>)</td><td>{} (+<b>{}</b> &rArr; \
-                 <b>{}</b>, <b>{}</b>)</td></tr>",
-                fmt_dt(p1),
-                fmt_timedelta(p1 - unlock),
-                fmt_rank(rank_p1),
-                fmt_dt(p2),
-                fmt_timedelta(p2 - p1),
-                fmt_timedelta(p2 - unlock),
-                fmt_rank(rank_p2),
-            ),
+        let p2 = match *parts {
+            [Some(Completion { ts: p1, .. }), Some(Completion { ts, rank })] => {
+                let (ts, delta, time, (rank_html, rank)) = (
+                    fmt_dt(ts),
+                    fmt_timedelta(ts - p1),
+                    fmt_timedelta(ts - unlock),
+                    fmt_part_rank(rank),
+                );
+                Cell::html(
+                    html!(
+                        "{ts} (+<b>{delta}</b> &rArr; <b>{time}</b>{rank_html})",
+                        ts,
+                        delta,
+                        time,
+                        rank_html
+                    ),
+                    format!("{ts} (+{delta} ⇒ {time}{rank})"),
+                )
+            }
+            _ => Cell::new(""),
+        };
+        table.push([
+            Cell::new(d),
+            p1,
+            p2,
+            Cell::new(rank.map(|r| fmt_rank(r).to_string()).unwrap_or_default()),
+        ]);
+    }
+
+    let mut out = html!(
+        r#"
+{identity}
+{score}
+{streak}
+{table}
+<p><b>Rank History:</b> <code>{sparkline}</code></p>
+"#,
+        identity = identity.render(format),
+        score = score.render(format),
+        streak = streak.render(format),
+        table = table.render(format),
+        sparkline = fmt_sparkline(ranks, worst)
+    );
+
+    if !achievements.is_empty() {
+        let achievements = achievements
+            .iter()
+            .map(|a| format!("🏆 {a}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        out.push(html!(
+            "<p><b>Achievements:</b> {achievements}</p>",
+            achievements
+        ));
+    }
+
+    if !first_bloods.is_empty() {
+        let first_bloods = first_bloods
+            .iter()
+            .map(|(day, part2)| format!("Day {day}/{}", if *part2 { 2 } else { 1 }))
+            .collect::<Vec<_>>()
+            .join(", ");
+        out.push(html!(
+            "<p><b>First Bloods:</b> 🩸 {first_bloods}</p>",
+            first_bloods
+        ));
+    }
+
+    let last_update = timezone
+        .from_utc_datetime(&last_update.naive_utc())
+        .format_ymd_hms_z();
+    out.push(html!("<sup>Last update: {last_update}</sup>", last_update));
+
+    out
+}
+
+/// Returns the html and text suffix showing the rank of a completion, which is
+/// empty for non-competitive members.
+fn fmt_part_rank(rank: Option<usize>) -> (Html, String) {
+    match rank {
+        Some(rank) => {
+            let rank = fmt_rank(rank);
+            (html!(", <b>{rank}</b>", rank), format!(", {rank}"))
         }
-        .unwrap();
+        None => Default::default(),
     }
+}
 
-    write!(&mut out, "</table><sup>Last update: {last_update}</sup>",).unwrap();
+/// Find a leaderboard member by AoC id, AoC name or matrix user id. If none of
+/// these match, `mention` is used to look up a mentioned matrix user instead.
+pub fn find_member<'a>(
+    context: &Context,
+    leaderboard: &'a PrivateLeaderboard,
+    name: &str,
+    mention: impl FnOnce() -> Option<OwnedUserId>,
+) -> Option<&'a PrivateLeaderboardMember> {
+    leaderboard
+        .members
+        .values()
+        .find(|m| {
+            name.parse() == Ok(m.id)
+                || m.name
+                    .as_ref()
+                    .is_some_and(|n| n.to_lowercase() == name.to_lowercase())
+        })
+        .or_else(|| match name.parse::<OwnedUserId>() {
+            Ok(matrix) => find_member_by_matrix(context, leaderboard, &matrix),
+            Err(_) => find_member_by_matrix(context, leaderboard, &mention()?),
+        })
+}
 
-    room.reply_to(event, html_message(out)).await?;
+/// Find the leaderboard member of the given matrix user. If the user has
+/// multiple AoC accounts on the leaderboard, the best ranked one is returned.
+pub fn find_member_by_matrix<'a>(
+    context: &Context,
+    leaderboard: &'a PrivateLeaderboard,
+    matrix: &UserId,
+) -> Option<&'a PrivateLeaderboardMember> {
+    context.users.by_matrix.get(matrix).and_then(|u| {
+        leaderboard
+            .members
+            .values()
+            .filter(|m| u.aoc.contains(&m.id))
+            .min()
+    })
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::commands::aoc::testing::{config, last_update, leaderboard};
+
+    #[test]
+    fn snapshot() {
+        let config = config();
+        let users = ContextUsers::from_config(&config);
+        let competitive = |id| users.by_aoc.get(&id).is_none_or(|u| u.competitive);
+        let leaderboard = leaderboard();
+
+        let member = leaderboard.members.values().find(|m| m.id == 1).unwrap();
+        let mut v = super::view(
+            &leaderboard,
+            &users,
+            competitive,
+            member,
+            2024,
+            3,
+            config.local_timezone,
+        );
+        v.repo_metadata = Some(RepoMetadata {
+            language: Some("Rust".into()),
+            last_push: Some("2024-12-02T20:00:00Z".parse().unwrap()),
+        });
+        v.achievements = vec!["Speed Demon".into()];
+        v.first_bloods = vec![(2, true)];
+        insta::assert_snapshot!(
+            "alice",
+            render(
+                &v,
+                &config,
+                TableFormat::Table,
+                config.local_timezone,
+                last_update()
+            )
+            .as_str()
+        );
+
+        let member = leaderboard.members.values().find(|m| m.id == 3).unwrap();
+        let v = super::view(
+            &leaderboard,
+            &users,
+            competitive,
+            member,
+            2024,
+            3,
+            config.local_timezone,
+        );
+        insta::assert_snapshot!(
+            "anonymous",
+            render(
+                &v,
+                &config,
+                TableFormat::Table,
+                config.local_timezone,
+                last_update()
+            )
+            .as_str()
+        );
+        insta::assert_snapshot!(
+            "anonymous_compact",
+            render(
+                &v,
+                &config,
+                TableFormat::Compact,
+                config.local_timezone,
+                last_update()
+            )
+            .as_str()
+        );
+
+        let member = leaderboard.members.values().find(|m| m.id == 4).unwrap();
+        let v = super::view(
+            &leaderboard,
+            &users,
+            competitive,
+            member,
+            2024,
+            3,
+            config.local_timezone,
+        );
+        assert!(v.days.iter().flatten().flatten().all(|c| c.rank.is_none()));
+        insta::assert_snapshot!(
+            "non_competitive",
+            render(
+                &v,
+                &config,
+                TableFormat::Text,
+                config.local_timezone,
+                last_update()
+            )
+            .as_str()
+        );
+    }
 }
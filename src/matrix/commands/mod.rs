@@ -26,8 +26,22 @@ pub async fn handle(
         "join" => aoc::join::invoke(event, room, &context).await,
         "leaderboard" | "lb" => aoc::leaderboard::invoke(event, room, &context, cmd).await,
         "day" => aoc::day::invoke(event, room, &context, cmd).await,
+        "halloffame" | "hof" => aoc::hall_of_fame::invoke(event, room, &context, cmd).await,
+        "next" => aoc::next::invoke(event, room, &context, cmd).await,
+        "stats" => aoc::stats::invoke(event, room, &context, cmd).await,
+        "calendar" | "cal" => aoc::calendar::invoke(event, room, &context, cmd).await,
+        "streaks" => aoc::streaks::invoke(event, room, &context, cmd).await,
         "user" => aoc::user::invoke(event, room, &context, cmd).await,
-        "solutions" | "repos" => aoc::solutions::invoke(event, room, &context).await,
+        "achievements" => aoc::achievements::invoke(event, room, &context, cmd).await,
+        "rankhistory" => aoc::rank_history::invoke(event, room, &context, cmd).await,
+        "chart" => aoc::chart::invoke(event, room, &context, cmd).await,
+        "compare" | "vs" => aoc::compare::invoke(event, room, &context, cmd).await,
+        "competitive" => aoc::competitive::invoke(event, room, &context, cmd).await,
+        "prefs" => aoc::prefs::invoke(event, room, &context, cmd).await,
+        "teams" => aoc::teams::invoke(event, room, &context, cmd).await,
+        "bench" => aoc::bench::invoke(event, room, &context, cmd).await,
+        "solutions" | "repos" => aoc::solutions::invoke(event, room, &context, cmd).await,
+        "review" => aoc::review::invoke(event, room, &context, cmd).await,
         "clear-cache" | "cc" => aoc::clear_cache::invoke(event, room, &context).await,
 
         // General
@@ -59,11 +73,26 @@ pub async fn help(
 
 #### Advent of Code
 - `{prefix}join` - Request instructions to join the private leaderboard
-- `{prefix}leaderboard [year={default_year}] [rows={default_rows}] [offset=0]` - Show the given slice of the private leaderboard
-- `{prefix}day [day{default_day}] [year={default_year}] [p=1|2|both] [rows={default_rows}] [offset=0]` - Show the given slice of the daily private leaderboard
-- `{prefix}user [user] [year={default_year}]` - Show statistics of the given user
-- `{prefix}solutions` - Show the list of solution repositories
+- `{prefix}leaderboard [year={default_year}|all] [rows={default_rows}] [offset=0] [format=table|compact|text]` - Show the given slice of the private leaderboard (of a single year or aggregated over all years)
+- `{prefix}day [day{default_day}] [year={default_year}] [p=1|2|both] [rows={default_rows}] [offset=0] [format=table|compact|text]` - Show the given slice of the daily private leaderboard
+- `{prefix}halloffame [rows={default_rows}] [format=table|compact|text]` - Show the winners of all years and the all-time totals
+- `{prefix}next [ics] [format=table|compact|text]` - Show the countdown to the next puzzle unlock and the remaining unlock schedule (or upload it as an iCalendar file)
+- `{prefix}stats [day{default_day}] [year={default_year}] [format=table|compact|text]` - Show solve time statistics of the given day, or rank the days of the given year by difficulty if no day is specified
+- `{prefix}calendar [year={default_year}] [rows={default_rows}] [offset=0] [format=table|compact|text]` - Show the collected stars of the given slice of the private leaderboard
+- `{prefix}streaks [year={default_year}] [rows={default_rows}] [format=table|compact|text]` - Rank the members by their current streak of consecutive days solved
+- `{prefix}user [user] [year={default_year}] [format=table|compact|text]` - Show statistics of the given user
+- `{prefix}achievements [year={default_year}] [format=table|compact|text]` - Show all achievements and who has unlocked them
+- `{prefix}rankhistory [user] [year={default_year}] [format=table|compact|text]` - Show the rank of the given user after each day, including their biggest climbs and drops
+- `{prefix}chart [year={default_year}] [top=10] [mode=score|stars|times]` - Render a chart of the local score or stars over time, or of the solve times of each day, of the top members
+- `{prefix}compare <user1> <user2> [year={default_year}] [format=table|compact|text]` - Compare the solve times of two users
+- `{prefix}solutions [year={default_year}] [lang] [rows={default_rows}] [offset=0] [format=table|compact|text]` - Show the given slice of the list of solution repositories (optionally only those using the given language)
+- `{prefix}competitive [on|off]` - Show or change whether you are included in rankings (your stars are still shown separately)
+- `{prefix}prefs [timezone|format|ping|language <value>|reset] [dm <unlock|solves|joins|reset> [on|off]]` - Show or change your preferences: the timezone of timestamps (e.g. `+01:00`), the table format, whether you are pinged in notifications, an additional language of your solutions and which notifications you also receive as direct messages (configured users only)
+- `{prefix}teams [year={default_year}] [day] [mode=sum|avg] [format=table|compact|text]` - Rank the teams by the summed or averaged scores of their members (of the whole year or a single day)
+- `{prefix}bench <day> <part> <duration> [lang] [year={default_year}]` - Report the runtime of your solution (e.g. `1.5ms`)
+- `{prefix}bench board [day] [year={default_year}] [format=table|compact|text]` - Show the fastest reported runtimes per day and language
 - `{prefix}clear-cache` - Clear the leaderboard cache (admin only)
+- `{prefix}review [list|dismiss <id>|noncompetitive <id>]` - List suspicious solves in a direct message or dismiss them or mark the member as non-competitive (admin only)
 
 #### General
 - `{prefix}ping` - Check bot health
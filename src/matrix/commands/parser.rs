@@ -19,7 +19,7 @@ impl<'a> ParsedCommand<'a> {
     }
 }
 
-pub fn parse(cmd: &str) -> ParsedCommand {
+pub fn parse(cmd: &str) -> ParsedCommand<'_> {
     let mut iter = cmd
         .chars()
         .scan(0, |s, c| {
@@ -1,11 +1,29 @@
-use std::future::Future;
+use std::{future::Future, sync::LazyLock};
 
+use chrono::FixedOffset;
 use matrix_sdk::{
     ruma::{
         api::client::message::send_message_event,
-        events::room::message::{OriginalRoomMessageEvent, Relation, RoomMessageEventContent},
+        events::{
+            room::message::{
+                MessageFormat, MessageType, OriginalRoomMessageEvent, Relation,
+                RoomMessageEventContent,
+            },
+            Mentions,
+        },
+        matrix_uri::MatrixId,
+        MatrixToUri, OwnedUserId, UserId,
     },
-    Room,
+    Client, Room,
+};
+use regex::Regex;
+use tracing::warn;
+
+use crate::{
+    aoc::models::PrivateLeaderboardMember,
+    context::Context,
+    prefs::DmSubscription,
+    utils::html::{html, link, to_plaintext, Html},
 };
 
 pub trait RoomExt {
@@ -32,22 +50,152 @@ impl RoomExt for Room {
     }
 }
 
-pub fn message(text: impl AsRef<str> + Into<String>) -> RoomMessageEventContent {
-    RoomMessageEventContent::text_markdown(text)
+/// Returns the direct message room with the given user, creating it if it does
+/// not exist yet.
+pub async fn dm_room(client: &Client, user: &UserId) -> anyhow::Result<Room> {
+    match client.get_dm_room(user) {
+        Some(room) => Ok(room),
+        None => Ok(client.create_dm(user).await?),
+    }
+}
+
+/// Send a notification to the direct message rooms of all users subscribed to
+/// it. The content is rendered in the timezone of each subscriber.
+pub async fn notify_subscribers(
+    context: &Context,
+    subscription: DmSubscription,
+    content: impl Fn(FixedOffset) -> RoomMessageEventContent,
+) {
+    let client = context.room.client();
+    for (user, prefs) in context.prefs.subscribers(subscription).await {
+        if !context.users.by_matrix.contains_key(&user) {
+            continue;
+        }
+        let timezone = prefs
+            .timezone
+            .map_or(context.config.local_timezone, |tz| tz.0);
+        let result = async {
+            let room = dm_room(&client, &user).await?;
+            room.send(content(timezone)).await?;
+            anyhow::Ok(())
+        }
+        .await;
+        if let Err(err) = result {
+            warn!("Failed to send {subscription} notification to {user}: {err}");
+        }
+    }
+}
+
+/// Returns a pill linking to the given matrix user, titled `name`, which
+/// should be the display name of the user in the room the pill is sent to.
+pub fn user_pill(user: &UserId, name: &str) -> Html {
+    link(user.matrix_to_uri(), name)
+}
+
+/// Returns the display name of the given user in the room, falling back to
+/// the user id if the user is not a member.
+pub async fn room_display_name(room: &Room, user: &UserId) -> String {
+    match room.get_member_no_sync(user).await {
+        Ok(Some(m)) => m.name().to_owned(),
+        _ => user.to_string(),
+    }
+}
+
+/// Returns the matrix user mentioned with a pill titled `text` in the given
+/// message, e.g. to resolve a command argument that was entered as a pill.
+pub fn find_pill(content: &RoomMessageEventContent, text: &str) -> Option<OwnedUserId> {
+    static PILL_REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"<a\s+href="([^"]*)"\s*>(.*?)</a>"#).unwrap());
+
+    let MessageType::Text(content) = &content.msgtype else {
+        return None;
+    };
+    let formatted = content
+        .formatted
+        .as_ref()
+        .filter(|f| f.format == MessageFormat::Html)?;
+    PILL_REGEX.captures_iter(&formatted.body).find_map(|c| {
+        if to_plaintext(&c[2]) != text.trim() {
+            return None;
+        }
+        match MatrixToUri::parse(&c[1]).ok()?.id() {
+            MatrixId::User(user) => Some(user.clone()),
+            _ => None,
+        }
+    })
 }
 
-pub fn notice(text: impl AsRef<str> + Into<String>) -> RoomMessageEventContent {
-    RoomMessageEventContent::notice_markdown(text)
+/// Returns the html name of a leaderboard member for notifications and the
+/// matrix user to add to `m.mentions`, if any. Members linked to a matrix user
+/// are mentioned with a pill titled with their display name in the room,
+/// unless they opted out of being pinged.
+pub async fn member_mention(
+    room: &Room,
+    context: &Context,
+    member: &PrivateLeaderboardMember,
+) -> (Html, Option<OwnedUserId>) {
+    let name = member.display_name();
+    let matrix = context
+        .users
+        .by_aoc
+        .get(&member.id)
+        .and_then(|u| u.matrix.clone());
+    let matrix = match matrix {
+        Some(matrix) if context.ping(&matrix).await => matrix,
+        _ => return (html!("<b>{name}</b>", name), None),
+    };
+
+    let room_name = room_display_name(room, &matrix).await;
+    let pill = user_pill(&matrix, &room_name);
+    let html = if room_name == name {
+        pill
+    } else {
+        html!("{pill} ({name})", pill, name)
+    };
+    (html, Some(matrix))
+}
+
+// All messages set `m.mentions` explicitly, so clients don't fall back to
+// pinging users whose names appear in the body. Notifications add the users
+// they intend to ping.
+
+pub fn message(text: impl AsRef<str> + Into<String>) -> RoomMessageEventContent {
+    RoomMessageEventContent::text_markdown(text).add_mentions(Mentions::new())
 }
 
-pub fn html_notice(text: impl Into<String> + Clone) -> RoomMessageEventContent {
-    RoomMessageEventContent::notice_html(text.clone(), text)
+pub fn html_notice(html: Html) -> RoomMessageEventContent {
+    RoomMessageEventContent::notice_html(to_plaintext(html.as_str()), html)
+        .add_mentions(Mentions::new())
 }
 
-pub fn html_message(html: impl Into<String> + Clone) -> RoomMessageEventContent {
-    RoomMessageEventContent::text_html(html.clone(), html)
+pub fn html_message(html: Html) -> RoomMessageEventContent {
+    RoomMessageEventContent::text_html(to_plaintext(html.as_str()), html)
+        .add_mentions(Mentions::new())
 }
 
 pub fn error_message(text: impl AsRef<str>) -> RoomMessageEventContent {
     message(format!("❌ Error: {}", text.as_ref()))
 }
+
+/// An error message with html content, e.g. to include user input without
+/// interpreting it as markdown.
+pub fn html_error_message(html: Html) -> RoomMessageEventContent {
+    html_message(html!("❌ Error: {html}", html))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_pill() {
+        let content = RoomMessageEventContent::text_html(
+            "!compare Zed Amy",
+            r#"!compare <a href="https://matrix.to/#/@zed:example.com">Zed</a> <a href="https://matrix.to/#/%40amy%3Aexample.com">Amy</a>"#,
+        );
+        let pill = |text| super::find_pill(&content, text).map(|u| u.to_string());
+        assert_eq!(pill("Amy").as_deref(), Some("@amy:example.com"));
+        assert_eq!(pill("Zed").as_deref(), Some("@zed:example.com"));
+        assert_eq!(pill("Bob"), None);
+    }
+}
@@ -0,0 +1,144 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use chrono::FixedOffset;
+use matrix_sdk::ruma::{OwnedUserId, UserId};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::utils::{serde::via_string::ViaString, store::Store, table::TableFormat};
+
+const PREFS_STORE_KEY: &[u8] = b"prefs";
+
+/// Notifications that users can subscribe to as direct messages, in addition
+/// to the ones sent to the room.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DmSubscription {
+    Unlock,
+    Solves,
+    Joins,
+}
+
+impl DmSubscription {
+    pub const ALL: [Self; 3] = [Self::Unlock, Self::Solves, Self::Joins];
+}
+
+impl FromStr for DmSubscription {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unlock" => Ok(Self::Unlock),
+            "solves" => Ok(Self::Solves),
+            "joins" => Ok(Self::Joins),
+            _ => Err(anyhow::anyhow!("Invalid notification: {s}")),
+        }
+    }
+}
+
+impl Display for DmSubscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Unlock => "unlock",
+            Self::Solves => "solves",
+            Self::Joins => "joins",
+        })
+    }
+}
+
+/// Settings of a matrix user set via the `prefs` command. Unset values fall
+/// back to the users config and the room defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Prefs {
+    /// Timezone of timestamps in responses and direct messages.
+    pub timezone: Option<ViaString<FixedOffset>>,
+    /// Rendering of tables in responses.
+    pub format: Option<TableFormat>,
+    /// Whether the user is mentioned (pinged) in notifications.
+    pub ping: Option<bool>,
+    pub dm: BTreeSet<DmSubscription>,
+    /// The (programming) language of the user's solutions.
+    pub language: Option<String>,
+}
+
+/// Preferences of matrix users, keyed by matrix user id.
+pub struct Preferences {
+    prefs: RwLock<HashMap<OwnedUserId, Prefs>>,
+    store: Store,
+}
+
+impl Preferences {
+    pub async fn new(store: Store) -> anyhow::Result<Self> {
+        let prefs = store
+            .get::<HashMap<OwnedUserId, Prefs>>(PREFS_STORE_KEY)
+            .await?
+            .unwrap_or_default();
+
+        Ok(Self {
+            prefs: prefs.into(),
+            store,
+        })
+    }
+
+    pub async fn get(&self, user: &UserId) -> Prefs {
+        self.prefs
+            .read()
+            .await
+            .get(user)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the users subscribed to the given notification and their
+    /// preferences.
+    pub async fn subscribers(&self, subscription: DmSubscription) -> Vec<(OwnedUserId, Prefs)> {
+        self.prefs
+            .read()
+            .await
+            .iter()
+            .filter(|(_, p)| p.dm.contains(&subscription))
+            .map(|(u, p)| (u.clone(), p.clone()))
+            .collect()
+    }
+
+    pub async fn set(&self, user: &UserId, prefs: Prefs) -> anyhow::Result<()> {
+        let mut guard = self.prefs.write().await;
+        if prefs == Prefs::default() {
+            guard.remove(user);
+        } else {
+            guard.insert(user.to_owned(), prefs);
+        }
+        self.store
+            .set::<HashMap<OwnedUserId, Prefs>>(PREFS_STORE_KEY, &guard)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let prefs = Prefs {
+            timezone: Some(ViaString("+05:30".parse().unwrap())),
+            format: Some(TableFormat::Compact),
+            ping: Some(false),
+            dm: [DmSubscription::Unlock, DmSubscription::Joins].into(),
+            language: Some("Rust".into()),
+        };
+        let bytes = rmp_serde::to_vec(&prefs).unwrap();
+        assert_eq!(rmp_serde::from_slice::<Prefs>(&bytes).unwrap(), prefs);
+
+        for subscription in DmSubscription::ALL {
+            assert_eq!(
+                subscription.to_string().parse::<DmSubscription>().unwrap(),
+                subscription
+            );
+        }
+    }
+}
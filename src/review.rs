@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::{aoc::review::SuspiciousSolve, utils::store::Store};
+
+const REVIEW_QUEUE_STORE_KEY: &[u8] = b"review_queue";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewFlag {
+    pub id: u32,
+    pub solve: SuspiciousSolve,
+    pub status: ReviewStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewStatus {
+    Open,
+    Dismissed,
+    NonCompetitive,
+}
+
+/// Suspicious solves that have been flagged for review by an admin.
+pub struct ReviewQueue {
+    flags: RwLock<Vec<ReviewFlag>>,
+    store: Store,
+}
+
+impl ReviewQueue {
+    pub async fn new(store: Store) -> anyhow::Result<Self> {
+        let flags = store
+            .get::<Vec<ReviewFlag>>(REVIEW_QUEUE_STORE_KEY)
+            .await?
+            .unwrap_or_default();
+
+        Ok(Self {
+            flags: flags.into(),
+            store,
+        })
+    }
+
+    /// Add all solves that have not been flagged before (see
+    /// [`SuspiciousSolve::same_solves`]) to the queue and return the new flags.
+    pub async fn add(
+        &self,
+        solves: impl IntoIterator<Item = SuspiciousSolve>,
+    ) -> anyhow::Result<Vec<ReviewFlag>> {
+        let mut guard = self.flags.write().await;
+        let mut next_id = guard.iter().map(|f| f.id + 1).max().unwrap_or(1);
+        let mut new = Vec::new();
+        for solve in solves {
+            if guard.iter().any(|f| f.solve.same_solves(&solve)) {
+                continue;
+            }
+            let flag = ReviewFlag {
+                id: next_id,
+                solve,
+                status: ReviewStatus::Open,
+            };
+            next_id += 1;
+            guard.push(flag.clone());
+            new.push(flag);
+        }
+
+        if !new.is_empty() {
+            self.store
+                .set::<Vec<ReviewFlag>>(REVIEW_QUEUE_STORE_KEY, &guard)
+                .await?;
+        }
+
+        Ok(new)
+    }
+
+    pub async fn open(&self) -> Vec<ReviewFlag> {
+        self.flags
+            .read()
+            .await
+            .iter()
+            .filter(|f| f.status == ReviewStatus::Open)
+            .cloned()
+            .collect()
+    }
+
+    /// Resolve the open flag with the given id, or return `None` if there is no
+    /// such flag.
+    pub async fn resolve(
+        &self,
+        id: u32,
+        status: ReviewStatus,
+    ) -> anyhow::Result<Option<ReviewFlag>> {
+        let mut guard = self.flags.write().await;
+        let Some(flag) = guard
+            .iter_mut()
+            .find(|f| f.id == id && f.status == ReviewStatus::Open)
+        else {
+            return Ok(None);
+        };
+        flag.status = status;
+        let flag = flag.clone();
+
+        self.store
+            .set::<Vec<ReviewFlag>>(REVIEW_QUEUE_STORE_KEY, &guard)
+            .await?;
+
+        Ok(Some(flag))
+    }
+}
@@ -0,0 +1,65 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use tracing::{error, trace, warn};
+
+use crate::{forge::RepoMetadata, Context};
+
+const REPO_METADATA_STORE_KEY: &[u8] = b"forge_repo_metadata";
+
+pub async fn start(context: Arc<Context>) -> ! {
+    if let Some(metadata) = context
+        .store
+        .get::<HashMap<String, RepoMetadata>>(REPO_METADATA_STORE_KEY)
+        .await
+        .ok()
+        .flatten()
+    {
+        context.forge.update(metadata).await;
+    }
+
+    loop {
+        if let Err(err) = trigger(&context).await {
+            error!("Failed to update repository metadata: {err}");
+        }
+        tokio::time::sleep(Duration::from_secs(context.config.forge.interval)).await;
+    }
+}
+
+async fn trigger(context: &Context) -> anyhow::Result<()> {
+    trace!("updating repository metadata");
+
+    let repos = context
+        .config
+        .users
+        .iter()
+        .flat_map(|u| u.repo.all())
+        .collect::<BTreeSet<_>>();
+
+    let mut metadata = HashMap::new();
+    for repo in repos {
+        match context.forge.fetch(repo).await {
+            Some(Ok(m)) => {
+                metadata.insert(repo.to_owned(), m);
+            }
+            Some(Err(err)) => {
+                warn!("Failed to fetch metadata of repository {repo}: {err}");
+                if let Some(m) = context.forge.get(repo).await {
+                    metadata.insert(repo.to_owned(), m);
+                }
+            }
+            None => {}
+        }
+    }
+
+    context
+        .store
+        .set::<HashMap<String, RepoMetadata>>(REPO_METADATA_STORE_KEY, &metadata)
+        .await?;
+    context.forge.update(metadata).await;
+
+    Ok(())
+}
@@ -4,8 +4,11 @@ use std::{
 };
 
 use matrix_sdk::{
-    ruma::events::room::message::{
-        FormattedBody, ImageMessageEventContent, MessageType, RoomMessageEventContent,
+    ruma::events::{
+        room::message::{
+            FormattedBody, ImageMessageEventContent, MessageType, RoomMessageEventContent,
+        },
+        Mentions,
     },
     RoomState,
 };
@@ -15,7 +18,10 @@ use tracing::{error, trace, warn};
 
 use crate::{
     mastodon::{self, AttachmentType},
-    utils::datetime::now,
+    utils::{
+        datetime::now,
+        html::{decode_entities, html, to_plaintext},
+    },
     Context,
 };
 
@@ -105,22 +111,26 @@ async fn trigger(context: &Context, last_id: &mut mastodon::Id) -> anyhow::Resul
                     .await?;
 
                 let link_prefix = &context.config.matrix.link_prefix;
-                let caption = format!(
-                    r#"<a href="{link_prefix}{}">{}</a> (created by <a href="{link_prefix}{}">@{}</a>)"#,
-                    post.url,
-                    remove_html_tags(&post.content),
-                    post.account.url,
-                    post.account.username
+                let caption = html!(
+                    r#"<a href="{link_prefix}{url}">{content}</a> (created by <a href="{link_prefix}{account_url}">@{username}</a>)"#,
+                    link_prefix,
+                    url = post.url,
+                    content = decode_entities(&remove_html_tags(&post.content)),
+                    account_url = post.account.url,
+                    username = post.account.username
                 );
 
-                let mut image_message =
-                    ImageMessageEventContent::plain(caption.clone(), response.content_uri);
+                let mut image_message = ImageMessageEventContent::plain(
+                    to_plaintext(caption.as_str()),
+                    response.content_uri,
+                );
                 image_message.filename = Some(filename);
                 image_message.formatted = Some(FormattedBody::html(caption));
 
-                room.send(RoomMessageEventContent::new(MessageType::Image(
-                    image_message,
-                )))
+                room.send(
+                    RoomMessageEventContent::new(MessageType::Image(image_message))
+                        .add_mentions(Mentions::new()),
+                )
                 .await?;
             }
 
@@ -1,14 +1,16 @@
 use std::{sync::Arc, time::Duration};
 
-use matrix_sdk::{Room, RoomState};
+use matrix_sdk::{ruma::events::Mentions, Room, RoomState};
 use tracing::{error, info, trace, warn};
 
 use crate::{
     aoc::{
         day::AocDay,
-        models::{PrivateLeaderboard, PrivateLeaderboardMembers},
+        models::{AocId, PrivateLeaderboard, PrivateLeaderboardMembers},
     },
-    matrix::utils::notice,
+    matrix::utils::{html_notice, member_mention, notify_subscribers},
+    prefs::DmSubscription,
+    utils::html::html,
     Context,
 };
 
@@ -74,16 +76,24 @@ async fn send_notifications(
         return Ok(());
     }
 
+    // multiple AoC accounts of the same person count as one member
+    let contains_person = |leaderboard: &PrivateLeaderboardMembers, member: AocId| {
+        let person = context.users.canonical_aoc_id(member);
+        leaderboard
+            .values()
+            .any(|m| context.users.canonical_aoc_id(m.id) == person)
+    };
+
     let mut notifications = Vec::new();
 
-    for (id, member) in new_leaderboard {
-        if !old_leaderboard.contains_key(id) {
+    for member in new_leaderboard.values() {
+        if !contains_person(old_leaderboard, member.id) {
             notifications.push((member, true));
         }
     }
 
-    for (id, member) in old_leaderboard {
-        if !new_leaderboard.contains_key(id) {
+    for member in old_leaderboard.values() {
+        if !contains_person(new_leaderboard, member.id) {
             notifications.push((member, false));
         }
     }
@@ -94,19 +104,16 @@ async fn send_notifications(
     );
 
     for (member, joined) in notifications {
-        let matrix = context
-            .users
-            .by_aoc
-            .get(&member.id)
-            .and_then(|m| m.matrix.as_deref());
-
-        let name = member.matrix_mention_or_display_name(matrix);
+        let (name, mention) = member_mention(room, context, member).await;
         let action = if joined { "joined" } else { "left" };
-
-        room.send(notice(format!(
-            "{name} has {action} the private leaderboard"
-        )))
-        .await?;
+        let html = html!("{name} has {action} the private leaderboard", name, action);
+
+        room.send(html_notice(html.clone()).add_mentions(Mentions::with_user_ids(mention)))
+            .await?;
+        notify_subscribers(context, DmSubscription::Joins, |_| {
+            html_notice(html.clone())
+        })
+        .await;
     }
 
     Ok(())
@@ -2,8 +2,11 @@ use std::sync::Arc;
 
 use crate::context::Context;
 
+mod forge_metadata;
 mod garygrady_posts;
 mod join_leave_notifications;
+mod review_flags;
+mod season_recap;
 mod solve_notifications;
 mod unlock_announcements;
 
@@ -12,4 +15,11 @@ pub fn start(context: Arc<Context>) {
     tokio::spawn(solve_notifications::start(Arc::clone(&context)));
     tokio::spawn(join_leave_notifications::start(Arc::clone(&context)));
     tokio::spawn(garygrady_posts::start(Arc::clone(&context)));
+    tokio::spawn(season_recap::start(Arc::clone(&context)));
+    if context.config.forge.enabled {
+        tokio::spawn(forge_metadata::start(Arc::clone(&context)));
+    }
+    if context.config.review.enabled {
+        tokio::spawn(review_flags::start(Arc::clone(&context)));
+    }
 }
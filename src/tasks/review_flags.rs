@@ -0,0 +1,74 @@
+use std::{sync::Arc, time::Duration};
+
+use tracing::{error, trace, warn};
+
+use crate::{
+    aoc::{day::AocDay, review::detect},
+    matrix::utils::{dm_room, html_notice},
+    utils::html::html,
+    Context,
+};
+
+pub async fn start(context: Arc<Context>) -> ! {
+    loop {
+        if let Err(err) = trigger(&context).await {
+            error!("Failed to check for suspicious solves: {err}");
+        }
+        tokio::time::sleep(Duration::from_secs(context.config.review.interval)).await;
+    }
+}
+
+async fn trigger(context: &Context) -> anyhow::Result<()> {
+    let year = AocDay::most_recent().year;
+
+    trace!(year, "checking for suspicious solves");
+
+    let leaderboard = context.aoc_client.get_private_leaderboard(year).await?.0;
+
+    let competitive = context.competitive().await;
+    let solves = detect(&leaderboard, year, &context.config.review)
+        .into_iter()
+        .filter(|s| competitive(s.member));
+    let flags = context.review.add(solves).await?;
+    if flags.is_empty() {
+        return Ok(());
+    }
+
+    trace!(?flags, "sending review flags to admins");
+
+    let mut out = html!("<b>New suspicious solves to review:</b><ul>");
+    for flag in &flags {
+        let id = flag.id;
+        let name = &flag.solve.name;
+        let year = flag.solve.year;
+        let reason = flag.solve.reason.description();
+        out.push(html!(
+            "<li><code>#{id}</code> {name} ({year}): {reason}</li>",
+            id,
+            name,
+            year,
+            reason
+        ));
+    }
+    let prefix = &context.config.matrix.command_prefix;
+    out.push(html!(
+        "</ul>Use <code>{prefix}review dismiss &lt;id&gt;</code> or <code>{prefix}review \
+         noncompetitive &lt;id&gt;</code> to resolve them.",
+        prefix
+    ));
+
+    let client = context.room.client();
+    for admin in &context.config.matrix.admin_ids {
+        let result = async {
+            let room = dm_room(&client, admin).await?;
+            room.send(html_notice(out.clone())).await?;
+            anyhow::Ok(())
+        }
+        .await;
+        if let Err(err) = result {
+            warn!("Failed to send review flags to {admin}: {err}");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use tracing::{error, info};
+
+use crate::{
+    aoc::{
+        day::AocDay,
+        stats::{competition_ranks, team_scores},
+    },
+    context::Context,
+    matrix::utils::html_message,
+    utils::{datetime::sleep_until, fmt::fmt_rank, html::html},
+};
+
+/// Number of members listed in the recap.
+const TOP: usize = 10;
+
+pub async fn start(context: Arc<Context>) -> ! {
+    loop {
+        let mut year = AocDay::most_recent().year;
+        if AocDay::is_over(year) {
+            year += 1;
+        }
+        let datetime = AocDay::event_end(year);
+        info!(year, ?datetime, "waiting until end of event");
+        sleep_until(datetime).await;
+        info!(year, "event is over, sending season recap");
+        if let Err(err) = trigger(&context, year).await {
+            error!("Failed to send season recap: {err}");
+        }
+    }
+}
+
+async fn trigger(context: &Context, year: i32) -> anyhow::Result<()> {
+    let leaderboard = context.aoc_client.get_private_leaderboard(year).await?.0;
+    let ranked = leaderboard.filtered(context.competitive().await);
+
+    let mut members = ranked
+        .members
+        .values()
+        .filter(|m| m.local_score > 0)
+        .collect::<Vec<_>>();
+    members.sort_unstable();
+
+    let mut out = html!("<h3>🎄 Advent of Code {year} is over! 🎄</h3>", year);
+
+    if !members.is_empty() {
+        out.push(html!("<b>Final Leaderboard</b><ul>"));
+        for (rank, member) in competition_ranks(members, |m| m.local_score).take(TOP) {
+            out.push(html!(
+                "<li>{rank} <b>{name}</b>: {score} points, {stars} stars</li>",
+                rank = fmt_rank(rank),
+                name = member.display_name(),
+                score = member.local_score,
+                stars = member.stars
+            ));
+        }
+        out.push(html!("</ul>"));
+    }
+
+    let full_completions = leaderboard
+        .members
+        .values()
+        .filter(|m| m.stars == 2 * AocDay::DAYS)
+        .map(|m| m.display_name())
+        .collect::<Vec<_>>();
+    if !full_completions.is_empty() {
+        out.push(html!(
+            "<b>Full Completions ({count})</b>: {names}<br>",
+            count = full_completions.len(),
+            names = full_completions.join(", ")
+        ));
+    }
+
+    let mut teams = team_scores(
+        &ranked,
+        None,
+        |id| context.users.team(id),
+        |id| context.users.canonical_aoc_id(id),
+    );
+    teams.sort_by_key(|t| std::cmp::Reverse(t.score));
+    if !teams.is_empty() {
+        out.push(html!("<b>Team Standings</b><ul>"));
+        for (rank, team) in competition_ranks(teams, |t| t.score) {
+            out.push(html!(
+                "<li>{rank} <b>{name}</b>: {score} points, {stars} stars</li>",
+                rank = fmt_rank(rank),
+                name = team.name,
+                score = team.score,
+                stars = team.stars
+            ));
+        }
+        out.push(html!("</ul>"));
+    }
+
+    context.room.send(html_message(out)).await?;
+    Ok(())
+}
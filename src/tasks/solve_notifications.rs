@@ -1,22 +1,38 @@
 use std::{sync::Arc, time::Duration};
 
-use chrono::{DateTime, TimeZone, Utc};
-use matrix_sdk::{Room, RoomState};
+use chrono::{DateTime, FixedOffset, TimeDelta, TimeZone, Utc};
+use matrix_sdk::{
+    ruma::{
+        events::{room::message::ReplacementMetadata, Mentions},
+        OwnedUserId,
+    },
+    Room, RoomState,
+};
 use tracing::{error, trace, warn};
 
 use crate::{
+    achievements::UnlockedAchievement,
     aoc::{
+        achievements::is_satisfied,
         day::AocDay,
         models::{PrivateLeaderboard, PrivateLeaderboardMember, PrivateLeaderboardMembers},
+        stats::Streaks,
     },
-    matrix::utils::html_notice,
+    config::AchievementRule,
+    first_blood::FirstBlood,
+    matrix::utils::{html_notice, member_mention, notify_subscribers},
+    prefs::DmSubscription,
     utils::{
         datetime::{now, DateTimeExt},
         fmt::{fmt_rank, fmt_timedelta},
+        html::{html, Html},
     },
     Context,
 };
 
+/// Streak lengths that are mentioned in solve notifications.
+const STREAK_MILESTONES: &[u32] = &[5, 10, 15, 20, 25];
+
 pub async fn start(context: Arc<Context>) -> ! {
     let mut year = AocDay::most_recent().year;
     let mut leaderboard = context
@@ -54,17 +70,10 @@ async fn trigger(
 
     let new_leaderboard = context.aoc_client.get_private_leaderboard(*year).await?.0;
 
-    send_notifications(
-        room,
-        context,
-        *year,
-        leaderboard
-            .as_ref()
-            .map(|l| &l.members)
-            .unwrap_or(&Default::default()),
-        &new_leaderboard.members,
-    )
-    .await?;
+    let empty = Default::default();
+    let old_members = leaderboard.as_ref().map(|l| &l.members).unwrap_or(&empty);
+    send_notifications(room, context, *year, old_members, &new_leaderboard.members).await?;
+    unlock_achievements(room, context, *year, old_members, &new_leaderboard).await?;
 
     *leaderboard = Some(new_leaderboard);
 
@@ -78,6 +87,7 @@ async fn send_notifications(
     old_leaderboard: &PrivateLeaderboardMembers,
     new_leaderboard: &PrivateLeaderboardMembers,
 ) -> anyhow::Result<()> {
+    let competitive = context.competitive().await;
     let mut notifications = Vec::new();
     for (id, member) in new_leaderboard {
         let Some(old_member) = old_leaderboard.get(id) else {
@@ -88,14 +98,17 @@ async fn send_notifications(
             let old_completion = old_member.completion_day_level.get(&day);
 
             if old_completion.is_none() {
-                let rank = new_leaderboard
-                    .values()
-                    .filter(|m| {
-                        m.completion_day_level
-                            .get(&day)
-                            .is_some_and(|c| c.fst.get_star_ts <= completion.fst.get_star_ts)
-                    })
-                    .count();
+                let rank = competitive(member.id).then(|| {
+                    new_leaderboard
+                        .values()
+                        .filter(|m| competitive(m.id))
+                        .filter(|m| {
+                            m.completion_day_level
+                                .get(&day)
+                                .is_some_and(|c| c.fst.get_star_ts <= completion.fst.get_star_ts)
+                        })
+                        .count()
+                });
                 notifications.push(Notification {
                     member,
                     part2: false,
@@ -110,15 +123,18 @@ async fn send_notifications(
                 .as_ref()
                 .filter(|_| old_completion.is_none_or(|oc| oc.snd.is_none()))
             {
-                let rank = new_leaderboard
-                    .values()
-                    .filter(|m| {
-                        m.completion_day_level
-                            .get(&day)
-                            .and_then(|c| c.snd.as_ref())
-                            .is_some_and(|c| c.get_star_ts <= part2.get_star_ts)
-                    })
-                    .count();
+                let rank = competitive(member.id).then(|| {
+                    new_leaderboard
+                        .values()
+                        .filter(|m| competitive(m.id))
+                        .filter(|m| {
+                            m.completion_day_level
+                                .get(&day)
+                                .and_then(|c| c.snd.as_ref())
+                                .is_some_and(|c| c.get_star_ts <= part2.get_star_ts)
+                        })
+                        .count()
+                });
                 notifications.push(Notification {
                     member,
                     part2: true,
@@ -136,7 +152,229 @@ async fn send_notifications(
 
     trace!(?notifications, "sending puzzle solve notifications");
     for notification in notifications {
-        room.send(html_notice(notification.to_string(context)))
+        match notification.rank {
+            Some(1) if context.config.first_blood.enabled => {
+                send_first_blood(room, context, notification).await?;
+            }
+            Some(2) if context.config.first_blood.enabled => {
+                send_notification(room, context, notification).await?;
+                update_first_blood_margin(room, context, notification).await?;
+            }
+            _ => send_notification(room, context, notification).await?,
+        }
+    }
+
+    Ok(())
+}
+
+async fn send_notification(
+    room: &Room,
+    context: &Context,
+    notification: Notification<'_>,
+) -> anyhow::Result<()> {
+    let (name, mention) = notification.name(room, context).await;
+    room.send(
+        html_notice(notification.to_html(context, &name, context.config.local_timezone))
+            .add_mentions(Mentions::with_user_ids(mention)),
+    )
+    .await?;
+    notify_subscribers(context, DmSubscription::Solves, |timezone| {
+        html_notice(notification.to_html(context, &name, timezone))
+    })
+    .await;
+    Ok(())
+}
+
+async fn send_first_blood(
+    room: &Room,
+    context: &Context,
+    notification: Notification<'_>,
+) -> anyhow::Result<()> {
+    let Notification {
+        member,
+        part2,
+        day,
+        ts,
+        ..
+    } = notification;
+
+    let (name, mention) = notification.name(room, context).await;
+    let mut record = FirstBlood {
+        year: day.year,
+        day: day.day,
+        part2,
+        member: context.users.canonical_aoc_id(member.id),
+        name,
+        time: (ts - day.unlock_datetime()).num_seconds(),
+        second: None,
+        event_id: None,
+    };
+    let html = fmt_first_blood(context, &record);
+    let response = room
+        .send(html_notice(html.clone()).add_mentions(Mentions::with_user_ids(mention)))
+        .await?;
+    notify_subscribers(context, DmSubscription::Solves, |_| {
+        html_notice(html.clone())
+    })
+    .await;
+    record.event_id = Some(response.event_id);
+    context.first_bloods.set(record).await
+}
+
+/// Add the margin to the second solver to the first blood announcement of the
+/// same puzzle part.
+async fn update_first_blood_margin(
+    room: &Room,
+    context: &Context,
+    notification: Notification<'_>,
+) -> anyhow::Result<()> {
+    let Notification {
+        member,
+        part2,
+        day,
+        ts,
+        ..
+    } = notification;
+
+    let Some(mut record) = context.first_bloods.get(day.year, day.day, part2).await else {
+        return Ok(());
+    };
+    if record.second.is_some() {
+        return Ok(());
+    }
+
+    let first_ts = day.unlock_datetime() + TimeDelta::seconds(record.time);
+    record.second = Some((member.display_name(), (ts - first_ts).num_seconds()));
+
+    if let Some(event_id) = &record.event_id {
+        let content = html_notice(fmt_first_blood(context, &record))
+            .make_replacement(ReplacementMetadata::new(event_id.clone(), None), None);
+        room.send(content).await?;
+    }
+
+    context.first_bloods.set(record).await
+}
+
+fn fmt_first_blood(context: &Context, record: &FirstBlood) -> Html {
+    let config = &context.config.first_blood;
+
+    let part = fmt_part(record.part2);
+
+    let day = AocDay {
+        year: record.year,
+        day: record.day,
+    };
+    let url = day.url();
+    let link_prefix = &context.config.matrix.link_prefix;
+    let puzzle = html!(
+        r#"<a href="{link_prefix}{url}"><b>AoC {year} Day {day}</b></a>"#,
+        link_prefix,
+        url,
+        year = day.year,
+        day = day.day
+    );
+
+    let time = fmt_timedelta(TimeDelta::seconds(record.time));
+
+    let margin = match &record.second {
+        Some((second, margin)) => Html::trusted(&config.margin_message).fill_template(&[
+            ("second", second),
+            ("margin", &fmt_timedelta(TimeDelta::seconds(*margin))),
+        ]),
+        None => Html::default(),
+    };
+
+    Html::trusted(&config.message).fill_template(&[
+        ("name", &record.name),
+        ("part", &part),
+        ("puzzle", &puzzle),
+        ("time", &time),
+        ("margin", &margin),
+    ])
+}
+
+/// The colored name of a puzzle part.
+fn fmt_part(part2: bool) -> Html {
+    if part2 {
+        html!(r##"<span data-mx-color="#ffff66">part two</span>"##)
+    } else {
+        html!(r##"<span data-mx-color="#9999cc">part one</span>"##)
+    }
+}
+
+/// Evaluate the achievement rules for all members of the new leaderboard and
+/// announce new achievements of members that have collected stars since the
+/// last check.
+async fn unlock_achievements(
+    room: &Room,
+    context: &Context,
+    year: i32,
+    old_leaderboard: &PrivateLeaderboardMembers,
+    new_leaderboard: &PrivateLeaderboard,
+) -> anyhow::Result<()> {
+    let most_recent = AocDay::most_recent();
+    let last_day = if year == most_recent.year {
+        most_recent.day
+    } else {
+        25
+    };
+
+    let now = now();
+    let mut candidates = Vec::new();
+    for member in new_leaderboard.members.values() {
+        for achievement in &context.config.achievements {
+            if is_satisfied(
+                achievement.rule,
+                new_leaderboard,
+                member,
+                year,
+                last_day,
+                context.config.local_timezone,
+            ) {
+                candidates.push((member, achievement));
+            }
+        }
+    }
+
+    let unlocked = context
+        .achievements
+        .unlock(candidates.iter().map(|(member, achievement)| {
+            let achievement = UnlockedAchievement {
+                id: achievement.id.clone(),
+                year,
+                unlocked_at: now,
+            };
+            (context.users.canonical_aoc_id(member.id), achievement)
+        }))
+        .await?;
+
+    trace!(?unlocked, "unlocked achievements");
+    for (aoc, unlocked) in unlocked {
+        let Some(&(member, achievement)) = candidates
+            .iter()
+            .find(|(m, a)| context.users.canonical_aoc_id(m.id) == aoc && a.id == unlocked.id)
+        else {
+            continue;
+        };
+        // the fastest gap is only awarded after the event, when nobody
+        // collects stars anymore
+        let changed = achievement.rule == AchievementRule::FastestGap
+            || old_leaderboard
+                .values()
+                .find(|m| m.id == member.id)
+                .is_some_and(|m| m.stars != member.stars);
+        if !changed {
+            continue;
+        }
+
+        let (name, mention) = member_mention(room, context, member).await;
+        let html = html!(
+            "🏆 {name} has unlocked the achievement <b>{title}</b> <i>({description})</i>",
+            name,
+            title = achievement.name,
+            description = achievement.description
+        );
+        room.send(html_notice(html).add_mentions(Mentions::with_user_ids(mention)))
             .await?;
     }
 
@@ -149,11 +387,22 @@ struct Notification<'a> {
     part2: bool,
     day: AocDay,
     ts: DateTime<Utc>,
-    rank: usize,
+    /// `None` if the member is not competitive.
+    rank: Option<usize>,
 }
 
 impl Notification<'_> {
-    fn to_string(self, context: &Context) -> String {
+    /// Returns the html name of the member and the matrix user to mention.
+    async fn name(self, room: &Room, context: &Context) -> (Html, Option<OwnedUserId>) {
+        let (mut name, mention) = member_mention(room, context, self.member).await;
+        if let Some(team) = context.users.team(self.member.id) {
+            name.push(html!(" <i>({team})</i>", team));
+        }
+        (name, mention)
+    }
+
+    /// Render the notification with the timestamp in `timezone`.
+    fn to_html(self, context: &Context, name: &Html, timezone: FixedOffset) -> Html {
         let Self {
             member,
             part2,
@@ -162,17 +411,7 @@ impl Notification<'_> {
             rank,
         } = self;
 
-        let matrix = context
-            .users
-            .by_aoc
-            .get(&member.id)
-            .and_then(|m| m.matrix.as_deref());
-
-        let part = if part2 {
-            "<span data-mx-color=\"#ffff66\">part two</span>"
-        } else {
-            "<span data-mx-color=\"#9999cc\">part one</span>"
-        };
+        let part = fmt_part(part2);
 
         let start = if part2 {
             member
@@ -186,22 +425,50 @@ impl Notification<'_> {
         };
         let delta = fmt_timedelta(ts - start);
 
+        let streak = if part2 {
+            String::new()
+        } else {
+            // the streak may continue beyond the solved day if it was solved late
+            let most_recent = AocDay::most_recent();
+            let last_day = if day.year == most_recent.year {
+                most_recent.day
+            } else {
+                25
+            };
+            let streak =
+                Streaks::compute(member, day.year, last_day, context.config.local_timezone)
+                    .solved
+                    .current;
+            if STREAK_MILESTONES.contains(&streak) {
+                format!(" 🔥 {streak} day streak!")
+            } else {
+                String::new()
+            }
+        };
+
         let url = day.url();
         let AocDay { year, day } = day;
-        let ts = context
-            .config
-            .local_timezone
+        let ts = timezone
             .from_utc_datetime(&ts.naive_utc())
             .format_ymd_hms_z();
 
-        let name = member.matrix_mention_or_display_name_html(matrix);
-
-        let rank = fmt_rank(rank);
+        let rank = rank
+            .map(|rank| format!("{}, ", fmt_rank(rank)))
+            .unwrap_or_default();
 
         let link_prefix = &context.config.matrix.link_prefix;
-        format!(
-            "{name} has solved <b>{part}</b> of <a href=\"{link_prefix}{url}\"><b>AoC {year} Day \
-             {day}</b></a> at {ts} ({rank}, {delta})"
+        html!(
+            r#"{name} has solved <b>{part}</b> of <a href="{link_prefix}{url}"><b>AoC {year} Day {day}</b></a> at {ts} ({rank}{delta}){streak}"#,
+            name,
+            part,
+            link_prefix,
+            url,
+            year,
+            day,
+            ts,
+            rank,
+            delta,
+            streak
         )
     }
 }
@@ -4,7 +4,11 @@ use matrix_sdk::RoomState;
 use tracing::{error, info, warn};
 
 use crate::{
-    aoc::day::AocDay, context::Context, matrix::utils::message, utils::datetime::sleep_until,
+    aoc::day::AocDay,
+    context::Context,
+    matrix::utils::{message, notify_subscribers},
+    prefs::DmSubscription,
+    utils::datetime::sleep_until,
 };
 
 pub async fn start(context: Arc<Context>) -> ! {
@@ -30,10 +34,11 @@ async fn trigger(context: &Context, day: AocDay) -> anyhow::Result<()> {
     let url = day.url();
     let AocDay { year, day } = day;
     let link_prefix = &context.config.matrix.link_prefix;
-    room.send(message(format!(
+    let text = format!(
         "✨ The puzzles of **Advent of Code {year} Day {day}** can now be solved at \
          [{url}]({link_prefix}{url}) ✨ <!-- 🎉 -->",
-    )))
-    .await?;
+    );
+    room.send(message(&text)).await?;
+    notify_subscribers(context, DmSubscription::Unlock, |_| message(&text)).await;
     Ok(())
 }
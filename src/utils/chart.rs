@@ -0,0 +1,168 @@
+use std::{ops::Range, sync::Once};
+
+use plotters::{
+    coord::Shift,
+    prelude::*,
+    style::{register_font, FontStyle},
+};
+
+pub const WIDTH: u32 = 1000;
+pub const HEIGHT: u32 = 600;
+
+const FONT: &[u8] = include_bytes!("../../assets/DejaVuSans.ttf");
+
+pub struct Series {
+    pub name: String,
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Render a line chart of the given series as a png image.
+pub fn line_chart(
+    title: &str,
+    x_desc: &str,
+    y_desc: &str,
+    x_range: Range<f64>,
+    series: &[Series],
+) -> anyhow::Result<Vec<u8>> {
+    let y_max = series
+        .iter()
+        .flat_map(|s| &s.points)
+        .map(|&(_, y)| y)
+        .fold(1.0, f64::max);
+
+    render(|root| {
+        let mut chart = ChartBuilder::on(&root)
+            .caption(title, ("sans-serif", 28))
+            .margin(20)
+            .x_label_area_size(50)
+            .y_label_area_size(60)
+            .build_cartesian_2d(x_range, 0.0..y_max * 1.05)?;
+        chart
+            .configure_mesh()
+            .x_desc(x_desc)
+            .y_desc(y_desc)
+            .draw()?;
+
+        for (i, s) in series.iter().enumerate() {
+            let style = Palette99::pick(i).stroke_width(2);
+            chart
+                .draw_series(LineSeries::new(s.points.iter().copied(), style))?
+                .label(&s.name)
+                .legend(move |(x, y)| PathElement::new([(x, y), (x + 20, y)], style));
+        }
+
+        draw_legend(&mut chart)
+    })
+}
+
+/// Render a scatter plot of the given series with a logarithmic y axis as a
+/// png image.
+pub fn scatter_chart(
+    title: &str,
+    x_desc: &str,
+    y_desc: &str,
+    x_range: Range<f64>,
+    series: &[Series],
+) -> anyhow::Result<Vec<u8>> {
+    let (y_min, y_max) = series
+        .iter()
+        .flat_map(|s| &s.points)
+        .fold((f64::INFINITY, 1.0f64), |(min, max), &(_, y)| {
+            (min.min(y), max.max(y))
+        });
+    let y_min = y_min.min(y_max).max(1e-3);
+
+    render(|root| {
+        let mut chart = ChartBuilder::on(&root)
+            .caption(title, ("sans-serif", 28))
+            .margin(20)
+            .x_label_area_size(50)
+            .y_label_area_size(60)
+            .build_cartesian_2d(x_range, (y_min / 1.25..y_max * 1.25).log_scale())?;
+        chart
+            .configure_mesh()
+            .x_desc(x_desc)
+            .y_desc(y_desc)
+            .draw()?;
+
+        for (i, s) in series.iter().enumerate() {
+            let style = Palette99::pick(i).filled();
+            chart
+                .draw_series(s.points.iter().map(|&point| Circle::new(point, 4, style)))?
+                .label(&s.name)
+                .legend(move |(x, y)| Circle::new((x + 10, y), 4, style));
+        }
+
+        draw_legend(&mut chart)
+    })
+}
+
+fn draw_legend<'a, DB, CT>(chart: &mut ChartContext<'a, DB, CT>) -> anyhow::Result<()>
+where
+    DB: DrawingBackend + 'a,
+    DB::ErrorType: 'static,
+    CT: CoordTranslate,
+{
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .label_font(("sans-serif", 16))
+        .draw()?;
+    Ok(())
+}
+
+fn render(
+    draw: impl FnOnce(DrawingArea<BitMapBackend<'_>, Shift>) -> anyhow::Result<()>,
+) -> anyhow::Result<Vec<u8>> {
+    static REGISTER_FONT: Once = Once::new();
+    REGISTER_FONT.call_once(|| {
+        assert!(
+            register_font("sans-serif", FontStyle::Normal, FONT).is_ok(),
+            "Failed to load bundled font"
+        );
+    });
+
+    let mut buffer = vec![0; (WIDTH * HEIGHT * 3) as usize];
+    {
+        let root = BitMapBackend::with_buffer(&mut buffer, (WIDTH, HEIGHT)).into_drawing_area();
+        root.fill(&WHITE)?;
+        draw(root.clone())?;
+        root.present()?;
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, WIDTH, HEIGHT);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&buffer)?;
+
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_png() {
+        let series = [
+            Series {
+                name: "foo".into(),
+                points: vec![(1.0, 0.0), (2.0, 3.0), (3.0, 5.0)],
+            },
+            Series {
+                name: "bar".into(),
+                points: vec![(1.5, 2.0), (2.5, 4.0)],
+            },
+        ];
+
+        for png in [
+            line_chart("Test", "x", "y", 1.0..3.0, &series).unwrap(),
+            scatter_chart("Test", "x", "y", 1.0..3.0, &series).unwrap(),
+        ] {
+            assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        }
+    }
+}
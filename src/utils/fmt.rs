@@ -1,4 +1,7 @@
-use std::fmt::{Display, Formatter};
+use std::{
+    fmt::{Display, Formatter},
+    time::Duration,
+};
 
 use chrono::TimeDelta;
 
@@ -39,6 +42,40 @@ pub fn fmt_timedelta(td: TimeDelta) -> impl Display {
     })
 }
 
+pub fn fmt_runtime(runtime: Duration) -> impl Display {
+    DisplayWith(move |f| {
+        let secs = runtime.as_secs_f64();
+        if runtime < Duration::from_micros(1) {
+            write!(f, "{}ns", runtime.as_nanos())
+        } else if runtime < Duration::from_millis(1) {
+            write!(f, "{:.1}µs", secs * 1e6)
+        } else if runtime < Duration::from_secs(1) {
+            write!(f, "{:.1}ms", secs * 1e3)
+        } else {
+            write!(f, "{secs:.2}s")
+        }
+    })
+}
+
+/// Render the given ranks as a sparkline in which better ranks have higher
+/// bars. `worst` is the worst possible rank.
+pub fn fmt_sparkline(ranks: &[Option<usize>], worst: usize) -> impl Display + '_ {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    DisplayWith(move |f| {
+        for rank in ranks {
+            let c = match *rank {
+                Some(rank) if worst > 1 => {
+                    BARS[(worst - rank.clamp(1, worst)) * (BARS.len() - 1) / (worst - 1)]
+                }
+                Some(_) => BARS[BARS.len() - 1],
+                None => '·',
+            };
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    })
+}
+
 struct DisplayWith<F>(F)
 where
     F: Fn(&mut Formatter) -> std::fmt::Result;
@@ -51,3 +88,32 @@ where
         (self.0)(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime() {
+        let fmt = |d| fmt_runtime(d).to_string();
+        assert_eq!(fmt(Duration::from_nanos(0)), "0ns");
+        assert_eq!(fmt(Duration::from_nanos(999)), "999ns");
+        assert_eq!(fmt(Duration::from_nanos(1_000)), "1.0µs");
+        assert_eq!(fmt(Duration::from_nanos(999_900)), "999.9µs");
+        assert_eq!(fmt(Duration::from_millis(1)), "1.0ms");
+        assert_eq!(fmt(Duration::from_micros(999_900)), "999.9ms");
+        assert_eq!(fmt(Duration::from_secs(1)), "1.00s");
+        assert_eq!(fmt(Duration::from_millis(12_345)), "12.35s");
+    }
+
+    #[test]
+    fn sparkline() {
+        assert_eq!(fmt_sparkline(&[], 10).to_string(), "");
+        assert_eq!(fmt_sparkline(&[], 0).to_string(), "");
+        assert_eq!(
+            fmt_sparkline(&[Some(1), None, Some(10), Some(20)], 10).to_string(),
+            "█·▁▁"
+        );
+        assert_eq!(fmt_sparkline(&[Some(1)], 1).to_string(), "█");
+    }
+}
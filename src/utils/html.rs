@@ -0,0 +1,526 @@
+use std::{
+    fmt::{self, Display, Write},
+    sync::LazyLock,
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// A fragment of html. Untrusted text (e.g. member names or repository urls)
+/// can only be added escaped, so html is safe by construction: markup comes
+/// from string literals (see [`html!`]) or is explicitly [trusted](Html::trusted).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Html(String);
+
+/// Values that can be interpolated into [`Html`]. Text is escaped, while html
+/// is inserted as is.
+pub trait ToHtml {
+    fn write_html(&self, out: &mut String);
+}
+
+impl<T: Display + ?Sized> ToHtml for T {
+    fn write_html(&self, out: &mut String) {
+        struct Writer<'a>(&'a mut String);
+        impl Write for Writer<'_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                for c in s.chars() {
+                    match c {
+                        '&' => self.0.push_str("&amp;"),
+                        '<' => self.0.push_str("&lt;"),
+                        '>' => self.0.push_str("&gt;"),
+                        '"' => self.0.push_str("&quot;"),
+                        '\'' => self.0.push_str("&#39;"),
+                        c => self.0.push(c),
+                    }
+                }
+                Ok(())
+            }
+        }
+        write!(Writer(out), "{self}").unwrap();
+    }
+}
+
+impl ToHtml for Html {
+    fn write_html(&self, out: &mut String) {
+        out.push_str(&self.0);
+    }
+}
+
+impl ToHtml for &Html {
+    fn write_html(&self, out: &mut String) {
+        out.push_str(&self.0);
+    }
+}
+
+impl Html {
+    /// Markup that is trusted because it is written by the admins of the bot,
+    /// e.g. message templates from the config.
+    pub fn trusted(markup: impl Into<String>) -> Self {
+        Self(markup.into())
+    }
+
+    /// Replace the `{placeholder}`s of the template with the matching values
+    /// in a single pass, so placeholders in the values are not expanded. Use
+    /// [`html!`] instead of calling this directly.
+    pub fn format(template: &'static str, values: &[(&str, &dyn ToHtml)]) -> Self {
+        Self::trusted(template).fill(values, true)
+    }
+
+    /// Replace the `{placeholder}`s of this template with the matching values
+    /// in a single pass, so placeholders in the values are not expanded.
+    /// Unknown placeholders are kept as is.
+    pub fn fill_template(&self, values: &[(&str, &dyn ToHtml)]) -> Self {
+        self.fill(values, false)
+    }
+
+    fn fill(&self, values: &[(&str, &dyn ToHtml)], strict: bool) -> Self {
+        static PLACEHOLDER_REGEX: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"\{(\w+)\}").unwrap());
+
+        let mut out = String::with_capacity(self.0.len());
+        let mut last = 0;
+        for c in PLACEHOLDER_REGEX.captures_iter(&self.0) {
+            let m = c.get(0).unwrap();
+            out.push_str(&self.0[last..m.start()]);
+            match values.iter().find(|&&(name, _)| name == &c[1]) {
+                Some((_, value)) => value.write_html(&mut out),
+                None if strict => panic!("Missing value for placeholder {}", m.as_str()),
+                None => out.push_str(m.as_str()),
+            }
+            last = m.end();
+        }
+        out.push_str(&self.0[last..]);
+        Self(out)
+    }
+
+    /// Append the given value, escaping text.
+    pub fn push(&mut self, value: impl ToHtml) {
+        value.write_html(&mut self.0);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Concatenate the given fragments, separated by `separator`.
+    pub fn join(items: impl IntoIterator<Item = Html>, separator: &'static str) -> Self {
+        let mut out = Self::default();
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                out.0.push_str(separator);
+            }
+            out.push(item);
+        }
+        out
+    }
+}
+
+impl From<Html> for String {
+    fn from(html: Html) -> Self {
+        html.0
+    }
+}
+
+impl FromIterator<Html> for Html {
+    fn from_iter<I: IntoIterator<Item = Html>>(iter: I) -> Self {
+        Self::join(iter, "")
+    }
+}
+
+/// Build [`Html`] from a template literal whose `{placeholder}`s are replaced
+/// by the given values, which are escaped unless they are [`Html`] already.
+/// Values are given by name, either as `name = value` or as a variable `name`,
+/// e.g. `html!("<b>{name}</b> solved {part}", name = member.name, part)`.
+macro_rules! html {
+    (@value $name:ident) => {
+        $name
+    };
+    (@value $name:ident = $value:expr) => {
+        $value
+    };
+    ($template:literal $(, $name:ident $(= $value:expr)?)* $(,)?) => {
+        $crate::utils::html::Html::format(
+            $template,
+            &[$((
+                stringify!($name),
+                &$crate::utils::html::html!(@value $name $(= $value)?)
+                    as &dyn $crate::utils::html::ToHtml,
+            )),*],
+        )
+    };
+}
+pub(crate) use html;
+
+/// Escape the given untrusted text (e.g. member names or repository urls).
+pub fn escape(text: impl Display) -> Html {
+    let mut html = Html::default();
+    html.push(text);
+    html
+}
+
+/// Returns a link to the given url with the given title, both escaped unless
+/// they are html already.
+pub fn link(href: impl ToHtml, title: impl ToHtml) -> Html {
+    html!(r#"<a href="{href}">{title}</a>"#, href, title)
+}
+
+/// Render the html subset used in messages as plain text, e.g. for the `body`
+/// of formatted messages. Tables are aligned, list items are prefixed with a
+/// bullet or their number and links are written as `title <url>`.
+pub fn to_plaintext(html: &str) -> String {
+    let mut renderer = Renderer::default();
+    for token in tokenize(html) {
+        match token {
+            Token::Text(text) => renderer.text(&decode_entities(text)),
+            Token::Open(name, attrs) => renderer.open(name, attrs),
+            Token::Close(name) => renderer.close(name),
+        }
+    }
+    renderer.finish()
+}
+
+enum Token<'a> {
+    Text(&'a str),
+    Open(&'a str, &'a str),
+    Close(&'a str),
+}
+
+fn tokenize(html: &str) -> impl Iterator<Item = Token<'_>> {
+    let mut rest = html;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let tag = rest
+            .strip_prefix('<')
+            .and_then(|r| Some(&r[..r.find('>')?]))
+            .filter(|t| t.starts_with(|c: char| c.is_ascii_alphabetic() || c == '/'));
+        let Some(tag) = tag else {
+            let end = rest
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| c == '<')
+                .map_or(rest.len(), |(i, _)| i);
+            let (text, r) = rest.split_at(end);
+            rest = r;
+            return Some(Token::Text(text));
+        };
+        rest = &rest[tag.len() + 2..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            return Some(Token::Close(name.trim()));
+        }
+        let tag = tag.trim_end_matches('/');
+        let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        Some(Token::Open(name, attrs))
+    })
+}
+
+#[derive(Default)]
+struct Renderer {
+    lines: Vec<String>,
+    line: String,
+    pre: bool,
+    /// The href of the current link and the length of `line` at its start.
+    link: Option<(String, usize)>,
+    /// For each (nested) list whether it is ordered and the number of items
+    /// so far.
+    lists: Vec<(bool, usize)>,
+    table: Option<Vec<Vec<String>>>,
+}
+
+impl Renderer {
+    fn text(&mut self, text: &str) {
+        if self.pre {
+            let mut lines = text.split('\n');
+            self.line.push_str(lines.next().unwrap());
+            for line in lines {
+                self.lines.push(std::mem::take(&mut self.line));
+                self.line.push_str(line);
+            }
+            return;
+        }
+
+        for (i, word) in text.split(char::is_whitespace).enumerate() {
+            if i > 0 && !self.line.is_empty() && !self.line.ends_with(' ') {
+                self.line.push(' ');
+            }
+            self.line.push_str(word);
+        }
+    }
+
+    fn open(&mut self, name: &str, attrs: &str) {
+        static HREF_REGEX: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r#"href\s*=\s*"([^"]*)""#).unwrap());
+
+        match name.to_ascii_lowercase().as_str() {
+            "br" => self.break_line(),
+            "pre" => {
+                self.block();
+                self.pre = true;
+            }
+            "a" => {
+                let href = HREF_REGEX
+                    .captures(attrs)
+                    .map(|c| decode_entities(&c[1]))
+                    .unwrap_or_default();
+                self.link = Some((href, self.line.len()));
+            }
+            "ul" | "ol" => {
+                self.block();
+                self.lists.push((name == "ol", 0));
+            }
+            "li" => {
+                self.block();
+                let prefix = match self.lists.last_mut() {
+                    Some((true, n)) => {
+                        *n += 1;
+                        format!("{n}. ")
+                    }
+                    _ => "- ".into(),
+                };
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                self.line = format!("{indent}{prefix}");
+            }
+            "table" => {
+                self.block();
+                self.table = Some(Vec::new());
+            }
+            "tr" => {
+                if let Some(table) = &mut self.table {
+                    table.push(Vec::new());
+                }
+            }
+            "td" | "th" => self.line.clear(),
+            name if is_block(name) => self.block(),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        match name.to_ascii_lowercase().as_str() {
+            "pre" => {
+                self.lines.push(std::mem::take(&mut self.line));
+                self.pre = false;
+            }
+            "a" => {
+                let Some((href, start)) = self.link.take() else {
+                    return;
+                };
+                let title = self.line[start..].trim();
+                if href.is_empty() || title == href {
+                    return;
+                }
+                if title.is_empty() {
+                    self.line.push_str(&href);
+                } else {
+                    self.line.push_str(&format!(" <{href}>"));
+                }
+            }
+            "ul" | "ol" => {
+                self.block();
+                self.lists.pop();
+            }
+            "td" | "th" => {
+                let cell = std::mem::take(&mut self.line).trim().to_owned();
+                if let Some(row) = self.table.as_mut().and_then(|t| t.last_mut()) {
+                    row.push(cell);
+                }
+            }
+            "table" => {
+                if let Some(table) = self.table.take() {
+                    self.lines.extend(align(&table));
+                }
+            }
+            name if is_block(name) => self.block(),
+            _ => {}
+        }
+    }
+
+    fn break_line(&mut self) {
+        self.lines
+            .push(std::mem::take(&mut self.line).trim_end().to_owned());
+    }
+
+    fn block(&mut self) {
+        if self.table.is_none() && !self.line.trim().is_empty() {
+            self.break_line();
+        }
+        self.line.clear();
+    }
+
+    fn finish(mut self) -> String {
+        self.block();
+        self.lines
+            .iter()
+            .map(|l| l.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_matches('\n')
+            .to_owned()
+    }
+}
+
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "p" | "div"
+            | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "details"
+            | "summary"
+            | "blockquote"
+    )
+}
+
+/// Align the cells of the given rows into columns.
+fn align(rows: &[Vec<String>]) -> Vec<String> {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|r| r.get(i))
+                .map(|c| c.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_owned()
+        })
+        .collect()
+}
+
+/// Decode the html entities in the given text, e.g. in text from which html
+/// tags have been stripped.
+pub fn decode_entities(text: &str) -> String {
+    static ENTITY_REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"&(#[0-9]+|#x[0-9a-fA-F]+|[a-z]+);").unwrap());
+    ENTITY_REGEX
+        .replace_all(text, |c: &regex::Captures| {
+            let entity = &c[1];
+            let decoded = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .map(|n| u32::from_str_radix(n, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| c[0].to_owned(), String::from)
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn escape() {
+        assert_eq!(
+            super::escape(r#"<b>"Tom" & 'Jerry'</b>"#).as_str(),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+        );
+        assert_eq!(
+            super::link(r#"https://example.com/"><script>"#, "<i>x</i>").as_str(),
+            r#"<a href="https://example.com/&quot;&gt;&lt;script&gt;">&lt;i&gt;x&lt;/i&gt;</a>"#
+        );
+    }
+
+    #[test]
+    fn html() {
+        let name = "<i>{part}</i>";
+        let part = super::html!("<b>{n}</b>", n = 1);
+        assert_eq!(
+            super::html!("{name} solved {part} in {time}s", name, part, time = 42).as_str(),
+            "&lt;i&gt;{part}&lt;/i&gt; solved <b>1</b> in 42s"
+        );
+
+        let template = super::Html::trusted("<b>{name}</b> solved {part}{unknown}");
+        assert_eq!(
+            template
+                .fill_template(&[("name", &name), ("part", &part)])
+                .as_str(),
+            "<b>&lt;i&gt;{part}&lt;/i&gt;</b> solved <b>1</b>{unknown}"
+        );
+    }
+
+    #[test]
+    fn to_plaintext() {
+        let html = r#"
+<h3>Private Leaderboard (Advent of Code 2024)</h3>
+<p>Some <b>bold</b> text &amp; a <a href="https://example.com/foo">link</a>.</p>
+<table>
+<tr> <th>Rank</th> <th>Name</th> <th>Repository</th> </tr>
+<tr>
+    <td><b>1.</b></td>
+    <td>Alice &lt;3</td>
+    <td><a href="https://example.com">alice/aoc</a></td>
+</tr>
+<tr>
+    <td>10.</td>
+    <td>Bob</td>
+    <td><a href=""></a></td>
+</tr>
+</table>
+<ul>
+<li>one</li>
+<li>two<br>lines</li>
+</ul>
+<ol><li>first</li><li>second</li></ol>
+<pre><code>a  b
+c  d</code></pre>
+<sup>Last update: 2024-12-01 06:00:00</sup>
+"#;
+
+        assert_eq!(
+            super::to_plaintext(html),
+            "Private Leaderboard (Advent of Code 2024)
+Some bold text & a link <https://example.com/foo>.
+Rank  Name      Repository
+1.    Alice <3  alice/aoc <https://example.com>
+10.   Bob
+- one
+- two
+lines
+1. first
+2. second
+a  b
+c  d
+Last update: 2024-12-01 06:00:00"
+        );
+    }
+
+    #[test]
+    fn plain_links() {
+        assert_eq!(
+            super::to_plaintext(r#"<a href="https://example.com">https://example.com</a>"#),
+            "https://example.com"
+        );
+        assert_eq!(
+            super::to_plaintext("❌ 1 < 2 and <i>3 > 2</i> &#128512;"),
+            "❌ 1 < 2 and 3 > 2 😀"
+        );
+    }
+}
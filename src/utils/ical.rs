@@ -0,0 +1,92 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use super::datetime::now;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub uid: String,
+    pub start: DateTime<Utc>,
+    pub duration: TimeDelta,
+    pub summary: String,
+    pub url: Option<String>,
+}
+
+/// Render the given events as an iCalendar (RFC 5545) document.
+pub fn calendar(name: &str, events: &[Event]) -> String {
+    let dtstamp = fmt_datetime(now());
+
+    let mut out = String::new();
+    let mut line = |line: &str| write!(&mut out, "{line}\r\n").unwrap();
+
+    line("BEGIN:VCALENDAR");
+    line("VERSION:2.0");
+    line("PRODID:-//aocbot//aocbot//EN");
+    line(&format!("X-WR-CALNAME:{}", escape(name)));
+    for event in events {
+        line("BEGIN:VEVENT");
+        line(&format!("UID:{}", escape(&event.uid)));
+        line(&format!("DTSTAMP:{dtstamp}"));
+        line(&format!("DTSTART:{}", fmt_datetime(event.start)));
+        line(&format!(
+            "DTEND:{}",
+            fmt_datetime(event.start + event.duration)
+        ));
+        line(&format!("SUMMARY:{}", escape(&event.summary)));
+        if let Some(url) = &event.url {
+            line(&format!("URL:{url}"));
+        }
+        line("END:VEVENT");
+    }
+    line("END:VCALENDAR");
+
+    out
+}
+
+fn fmt_datetime(datetime: DateTime<Utc>) -> impl std::fmt::Display {
+    datetime.format("%Y%m%dT%H%M%SZ")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::datetime::set_now;
+
+    #[test]
+    fn calendar() {
+        let _guard = set_now("2024-11-30T12:00:00Z".parse().unwrap());
+
+        let events = [Event {
+            uid: "aoc-2024-01@aocbot".into(),
+            start: "2024-12-01T05:00:00Z".parse().unwrap(),
+            duration: TimeDelta::hours(1),
+            summary: "Advent of Code 2024, Day 1".into(),
+            url: Some("https://adventofcode.com/2024/day/1".into()),
+        }];
+
+        assert_eq!(
+            super::calendar("Advent of Code", &events),
+            "BEGIN:VCALENDAR\r\n\
+             VERSION:2.0\r\n\
+             PRODID:-//aocbot//aocbot//EN\r\n\
+             X-WR-CALNAME:Advent of Code\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:aoc-2024-01@aocbot\r\n\
+             DTSTAMP:20241130T120000Z\r\n\
+             DTSTART:20241201T050000Z\r\n\
+             DTEND:20241201T060000Z\r\n\
+             SUMMARY:Advent of Code 2024\\, Day 1\r\n\
+             URL:https://adventofcode.com/2024/day/1\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR\r\n"
+        );
+    }
+}
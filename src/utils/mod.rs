@@ -1,5 +1,9 @@
+pub mod chart;
 pub mod datetime;
 pub mod fmt;
+pub mod html;
+pub mod ical;
 pub mod regex_set_replacer;
 pub mod serde;
 pub mod store;
+pub mod table;
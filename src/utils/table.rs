@@ -0,0 +1,251 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::html::{escape, html, Html};
+
+/// How tabular data is rendered. Several (mostly mobile) matrix clients don't
+/// render html tables, so they can be replaced by an aligned monospace block
+/// or a list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableFormat {
+    #[default]
+    Table,
+    Compact,
+    Text,
+}
+
+impl FromStr for TableFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "compact" => Ok(Self::Compact),
+            "text" => Ok(Self::Text),
+            _ => Err(anyhow::anyhow!("Invalid table format: {s}")),
+        }
+    }
+}
+
+impl Display for TableFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Table => "table",
+            Self::Compact => "compact",
+            Self::Text => "text",
+        })
+    }
+}
+
+pub struct Cell {
+    html: Html,
+    text: String,
+}
+
+impl Cell {
+    /// A cell containing the given text, which is escaped in html.
+    pub fn new(text: impl Display) -> Self {
+        let text = text.to_string();
+        Self {
+            html: escape(&text),
+            text,
+        }
+    }
+
+    /// A cell that is rendered as `html` in html tables and lists, and as
+    /// `text` in monospace blocks.
+    pub fn html(html: Html, text: impl Display) -> Self {
+        Self {
+            html,
+            text: text.to_string(),
+        }
+    }
+
+    pub fn bold(self, bold: bool) -> Self {
+        if bold && !self.html.is_empty() {
+            Self {
+                html: html!("<b>{html}</b>", html = self.html),
+                ..self
+            }
+        } else {
+            self
+        }
+    }
+}
+
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    pub fn new(header: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            header: header.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, row: impl IntoIterator<Item = Cell>) {
+        self.rows.push(row.into_iter().collect());
+    }
+
+    pub fn render(&self, format: TableFormat) -> Html {
+        match format {
+            TableFormat::Table => self.render_table(),
+            TableFormat::Compact => self.render_compact(),
+            TableFormat::Text => self.render_text(),
+        }
+    }
+
+    fn render_table(&self) -> Html {
+        let header = Html::join(self.header.iter().map(|h| html!("<th>{h}</th>", h)), " ");
+        let mut out = html!("<table>\n<tr> {header} </tr>\n", header);
+        for row in &self.rows {
+            out.push(html!("<tr>\n"));
+            for cell in row {
+                out.push(html!("    <td>{cell}</td>\n", cell = cell.html));
+            }
+            out.push(html!("</tr>\n"));
+        }
+        out.push(html!("</table>"));
+        out
+    }
+
+    /// Render the table as an aligned monospace block, omitting columns that
+    /// are empty in every row.
+    fn render_compact(&self) -> Html {
+        let columns = (0..self.header.len())
+            .filter(|&i| self.rows.iter().any(|r| !r[i].text.is_empty()))
+            .map(|i| {
+                let width = self
+                    .rows
+                    .iter()
+                    .map(|r| r[i].text.chars().count())
+                    .chain([self.header[i].chars().count()])
+                    .max()
+                    .unwrap_or(0);
+                (i, width)
+            })
+            .collect::<Vec<_>>();
+
+        let line = |cells: &mut dyn Iterator<Item = &str>| {
+            let line = columns
+                .iter()
+                .zip(cells)
+                .map(|(&(_, width), cell)| format!("{cell:width$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            line.trim_end().to_owned()
+        };
+
+        let mut lines = vec![line(
+            &mut columns.iter().map(|&(i, _)| self.header[i].as_str()),
+        )];
+        for row in &self.rows {
+            lines.push(line(
+                &mut columns.iter().map(|&(i, _)| row[i].text.as_str()),
+            ));
+        }
+        html!("<pre><code>{lines}</code></pre>", lines = lines.join("\n"))
+    }
+
+    /// Render each row as a list item, omitting empty cells.
+    fn render_text(&self) -> Html {
+        let mut out = html!("<ul>\n");
+        for row in &self.rows {
+            let mut cells = self
+                .header
+                .iter()
+                .zip(row)
+                .filter(|(_, c)| !c.html.is_empty());
+            let Some((_, first)) = cells.next() else {
+                continue;
+            };
+            let rest = Html::join(
+                cells.map(|(h, c)| html!(" {h}: {html}", h, html = c.html)),
+                " ·",
+            );
+            out.push(html!("<li>{first}{rest}</li>\n", first = first.html, rest));
+        }
+        out.push(html!("</ul>"));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::html::link;
+
+    fn table() -> Table {
+        let mut table = Table::new(["Rank", "Name", "Repository"]);
+        table.push([
+            Cell::new("1.").bold(true),
+            Cell::new("Alice <3"),
+            Cell::html(link("https://example.com", "alice/aoc"), "alice/aoc"),
+        ]);
+        table.push([Cell::new("2."), Cell::new("Bob"), Cell::new("")]);
+        table
+    }
+
+    #[test]
+    fn render_table() {
+        assert_eq!(
+            table().render(TableFormat::Table).as_str(),
+            r#"<table>
+<tr> <th>Rank</th> <th>Name</th> <th>Repository</th> </tr>
+<tr>
+    <td><b>1.</b></td>
+    <td>Alice &lt;3</td>
+    <td><a href="https://example.com">alice/aoc</a></td>
+</tr>
+<tr>
+    <td>2.</td>
+    <td>Bob</td>
+    <td></td>
+</tr>
+</table>"#
+        );
+    }
+
+    #[test]
+    fn render_compact() {
+        assert_eq!(
+            table().render(TableFormat::Compact).as_str(),
+            "<pre><code>Rank  Name      Repository
+1.    Alice &lt;3  alice/aoc
+2.    Bob</code></pre>"
+        );
+
+        let mut table = Table::new(["Rank", "Name", "Matrix User"]);
+        table.push([Cell::new("1."), Cell::new("Alice"), Cell::new("")]);
+        assert_eq!(
+            table.render(TableFormat::Compact).as_str(),
+            "<pre><code>Rank  Name\n1.    Alice</code></pre>"
+        );
+    }
+
+    #[test]
+    fn render_text() {
+        assert_eq!(
+            table().render(TableFormat::Text).as_str(),
+            r#"<ul>
+<li><b>1.</b> Name: Alice &lt;3 · Repository: <a href="https://example.com">alice/aoc</a></li>
+<li>2. Name: Bob</li>
+</ul>"#
+        );
+
+        let mut table = Table::new(["Day", "Part 1"]);
+        table.push([Cell::new(1), Cell::new("")]);
+        assert_eq!(
+            table.render(TableFormat::Text).as_str(),
+            "<ul>\n<li>1</li>\n</ul>"
+        );
+    }
+}